@@ -0,0 +1,310 @@
+/*
+ * Approximate solutions to the knapsack problem, which come with an upper bound on the best
+ * possible value so that it's known how far from optimal they might be.
+ *
+ * The fully polynomial-time approximation scheme (FPTAS) rounds every value down to a multiple of
+ * some `K`, and solves the rounded problem exactly with dynamic programming over value (the
+ * table holds the lightest way to reach each rounded value).
+ * Each item loses less than `K` in the rounding, so the answer is within `n * K` of optimal.
+ *
+ * The textbook choice is `K = epsilon * max_value / n`, but that gives a table with O(n^2 / epsilon)
+ * columns.
+ * Instead we use `K = epsilon * lower / n`, where `lower` is the value of a quick greedy solution.
+ * The greedy solution is at least half of optimal, so the best rounded value is at most
+ * `2 * n / epsilon`, which is all the columns we need, while the error is still at most
+ * `epsilon * lower <= epsilon * optimal`.
+ *
+ * There are also some much quicker heuristics, which make no promise beyond the upper bound they
+ * report (the fractional relaxation):
+ * - taking items in order of value per unit weight while they fit,
+ * - the better of that and the single most valuable item, which is at least half of optimal,
+ * - starting from the latter, repeatedly adding an item or swapping a taken item for one that
+ *   wasn't, as long as that improves the value.
+ */
+
+use super::{Item, KnapsackProblem, KnapsackSolution, solution_from_flags};
+use super::branch_bound::{by_ratio, fractional_bound};
+
+/// The `ApproximateSolution` type -- a filling of the knapsack that may not be optimal, along
+/// with a limit on how good the optimal filling could be.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ApproximateSolution {
+    /// The filling of the knapsack that was found.
+    pub as_solution: KnapsackSolution,
+    /// No filling of the knapsack can have a value larger than this.
+    /// The gap to the optimum is at most `as_upper_bound - as_solution.ks_value`.
+    pub as_upper_bound: u64,
+}
+
+/*
+ * Indices of the items worth considering (with some value, and light enough to fit on their own),
+ * sorted by value per unit weight.
+ */
+fn useful_by_ratio(problem: &KnapsackProblem) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..problem.kp_options.len())
+        .filter(|&index| {
+            let item = &problem.kp_options[index];
+            item.item_value != 0 && item.item_weight <= problem.kp_capacity
+        })
+        .collect();
+    order.sort_by(|&left, &right| by_ratio(&problem.kp_options[left], &problem.kp_options[right]));
+    order
+}
+
+/*
+ * Upper bound on the value of any filling, from the fractional relaxation of the items in
+ * `order`.
+ */
+fn relaxation_bound(problem: &KnapsackProblem, order: &[usize]) -> u64 {
+    let sorted: Vec<Item> = order.iter().map(|&index| problem.kp_options[index].clone()).collect();
+    fractional_bound(&sorted, problem.kp_capacity as u64)
+}
+
+/*
+ * Go through `order` taking every item that still fits.
+ * Returns the flags for which items of `kp_options` were taken, and their total value.
+ */
+fn greedy_fill(problem: &KnapsackProblem, order: &[usize]) -> (Vec<bool>, u64) {
+    let mut keep      = vec![false; problem.kp_options.len()];
+    let mut remaining = problem.kp_capacity;
+    let mut value     = 0;
+    for &index in order {
+        let item = &problem.kp_options[index];
+        if item.item_weight <= remaining {
+            remaining  -= item.item_weight;
+            value      += item.item_value as u64;
+            keep[index] = true;
+        }
+    }
+    (keep, value)
+}
+
+/*
+ * The better of the greedy filling and the single most valuable item, which is always at least
+ * half of the optimal value.
+ */
+fn greedy_or_best_item(problem: &KnapsackProblem, order: &[usize]) -> (Vec<bool>, u64) {
+    let (keep, value) = greedy_fill(problem, order);
+    let best_item = order.iter()
+        .cloned()
+        .max_by_key(|&index| problem.kp_options[index].item_value);
+    match best_item {
+        Some(index) if problem.kp_options[index].item_value as u64 > value => {
+            let mut single = vec![false; problem.kp_options.len()];
+            single[index] = true;
+            (single, problem.kp_options[index].item_value as u64)
+        },
+        _ => (keep, value),
+    }
+}
+
+/// Returns a solution to the `KnapsackProblem` whose value is at least `(1 - epsilon)` times the
+/// optimal value, along with an upper bound on the optimal value.
+///
+/// This takes O(n^2 / epsilon) time and memory, however large the capacity and the values are.
+///
+/// # Panics
+/// If `epsilon` isn't positive.
+///
+/// ```
+/// use rust_algorithms::knapsack_problem::{approximate_knapsack, dynamic_knapsack, KnapsackProblem, Item};
+///
+/// let problem = KnapsackProblem {
+///     kp_capacity: 50,
+///     kp_options: vec![Item { item_weight: 10, item_value: 60 },
+///                      Item { item_weight: 20, item_value: 100 },
+///                      Item { item_weight: 30, item_value: 120 }],
+/// };
+/// let approximate = approximate_knapsack(problem.clone(), 0.1);
+/// let optimal = dynamic_knapsack(problem).ks_value as f64;
+/// assert!(approximate.as_solution.ks_value as f64 >= 0.9 * optimal);
+/// assert!(approximate.as_upper_bound as f64 >= optimal);
+/// ```
+pub fn approximate_knapsack(problem: KnapsackProblem, epsilon: f64)
+    -> ApproximateSolution {
+    if epsilon.is_nan() || epsilon <= 0.0 {
+        panic!("approximate_knapsack() given epsilon {}, which is not positive", epsilon);
+    }
+
+    let order = useful_by_ratio(&problem);
+    let upper = relaxation_bound(&problem, &order);
+    let (_, lower) = greedy_or_best_item(&problem, &order);
+    if order.is_empty() {
+        return ApproximateSolution {
+            as_solution: solution_from_flags(problem, &[]),
+            as_upper_bound: 0,
+        };
+    }
+
+    /*
+     * Rounding to multiples of less than one doesn't make the problem any smaller, so in that
+     * case we solve it exactly.
+     */
+    let scale   = (epsilon * lower as f64 / order.len() as f64).max(1.0);
+    let rounded: Vec<usize> = order.iter()
+        .map(|&index| (problem.kp_options[index].item_value as f64 / scale).floor() as usize)
+        .collect();
+    let columns = (upper as f64 / scale).floor() as usize + 1;
+
+    /*
+     * `lightest[p]` is the least weight needed to reach a rounded value of exactly `p` with the
+     * items so far, and `taken[row * columns + p]` records whether item `row` was used for it.
+     */
+    let mut lightest = vec![u64::MAX; columns];
+    let mut taken    = vec![false; order.len() * columns];
+    lightest[0] = 0;
+    for (row, &index) in order.iter().enumerate() {
+        let weight = problem.kp_options[index].item_weight as u64;
+        for profit in (rounded[row]..columns).rev() {
+            let previous = lightest[profit - rounded[row]];
+            if previous != u64::MAX && previous + weight < lightest[profit] {
+                lightest[profit] = previous + weight;
+                taken[row * columns + profit] = true;
+            }
+        }
+    }
+
+    let mut profit = (0..columns).rev()
+        .find(|&profit| lightest[profit] <= problem.kp_capacity as u64)
+        .unwrap_or(0);
+    let mut keep = vec![false; problem.kp_options.len()];
+    for (row, &index) in order.iter().enumerate().rev() {
+        if taken[row * columns + profit] {
+            keep[index] = true;
+            profit     -= rounded[row];
+        }
+    }
+
+    let solution = solution_from_flags(problem, &keep);
+    let error    = if scale > 1.0 { (scale * order.len() as f64).ceil() as u64 } else { 0 };
+    ApproximateSolution {
+        as_upper_bound: upper.min(solution.ks_value as u64 + error),
+        as_solution: solution,
+    }
+}
+
+/*
+ * Improve `keep` by adding a left-out item, or swapping a taken item for a left-out one, until
+ * neither improves the value.
+ * Each step increases the value, so this finishes, and each step takes O(n^2) time.
+ */
+fn improve_by_swaps(problem: &KnapsackProblem, order: &[usize], keep: &mut [bool]) {
+    let capacity = problem.kp_capacity as u64;
+    let mut weight: u64 = order.iter()
+        .filter(|&&index| keep[index])
+        .map(|&index| problem.kp_options[index].item_weight as u64)
+        .sum();
+    loop {
+        let mut improved = false;
+        for &added in order {
+            if keep[added] {
+                continue;
+            }
+            let item = &problem.kp_options[added];
+            if weight + item.item_weight as u64 <= capacity {
+                keep[added] = true;
+                weight     += item.item_weight as u64;
+                improved    = true;
+                continue;
+            }
+            let removed = order.iter().cloned().find(|&removed| {
+                let old = &problem.kp_options[removed];
+                keep[removed] && old.item_value < item.item_value &&
+                    weight - old.item_weight as u64 + item.item_weight as u64 <= capacity
+            });
+            if let Some(removed) = removed {
+                keep[removed] = false;
+                keep[added]   = true;
+                weight        = weight - problem.kp_options[removed].item_weight as u64 + item.item_weight as u64;
+                improved      = true;
+            }
+        }
+        if !improved {
+            break;
+        }
+    }
+}
+
+/*
+ * Run one of the heuristics, which turns the useful items (sorted by ratio) into the flags for
+ * the items taken.
+ */
+fn heuristic<F>(problem: KnapsackProblem, fill: F) -> ApproximateSolution
+    where F: Fn(&KnapsackProblem, &[usize]) -> Vec<bool> {
+    let order = useful_by_ratio(&problem);
+    let upper = relaxation_bound(&problem, &order);
+    let keep  = fill(&problem, &order);
+    let solution = solution_from_flags(problem, &keep);
+    ApproximateSolution {
+        as_upper_bound: upper.max(solution.ks_value as u64),
+        as_solution: solution,
+    }
+}
+
+/// Returns the solution found by taking items in order of value per unit weight, skipping any
+/// that don't fit, along with an upper bound on the optimal value.
+///
+/// This takes O(n log n) time, but the solution can be arbitrarily bad.
+///
+/// ```
+/// use rust_algorithms::knapsack_problem::{greedy_knapsack, KnapsackProblem, Item};
+///
+/// let approximate = greedy_knapsack(KnapsackProblem {
+///     kp_capacity: 10,
+///     kp_options: vec![Item { item_weight: 1, item_value: 2 },
+///                      Item { item_weight: 10, item_value: 10 }],
+/// });
+/// assert_eq!(approximate.as_solution.ks_value, 2);
+/// assert_eq!(approximate.as_upper_bound, 11);
+/// ```
+pub fn greedy_knapsack(problem: KnapsackProblem)
+    -> ApproximateSolution {
+    heuristic(problem, |problem, order| greedy_fill(problem, order).0)
+}
+
+/// Returns the better of `greedy_knapsack()` and the single most valuable item that fits, along
+/// with an upper bound on the optimal value.
+///
+/// This takes O(n log n) time, and the solution is always at least half of optimal.
+///
+/// ```
+/// use rust_algorithms::knapsack_problem::{greedy_best_item_knapsack, KnapsackProblem, Item};
+///
+/// let approximate = greedy_best_item_knapsack(KnapsackProblem {
+///     kp_capacity: 10,
+///     kp_options: vec![Item { item_weight: 1, item_value: 2 },
+///                      Item { item_weight: 10, item_value: 10 }],
+/// });
+/// assert_eq!(approximate.as_solution.ks_value, 10);
+/// ```
+pub fn greedy_best_item_knapsack(problem: KnapsackProblem)
+    -> ApproximateSolution {
+    heuristic(problem, |problem, order| greedy_or_best_item(problem, order).0)
+}
+
+/// Returns the solution of `greedy_best_item_knapsack()` after improving it by adding items and
+/// swapping single items, along with an upper bound on the optimal value.
+///
+/// The solution is never worse than `greedy_best_item_knapsack()`, and each improvement takes
+/// O(n^2) time.
+///
+/// ```
+/// use rust_algorithms::knapsack_problem::{local_search_knapsack, KnapsackProblem, Item};
+///
+/// let approximate = local_search_knapsack(KnapsackProblem {
+///     kp_capacity: 10,
+///     kp_options: vec![Item { item_weight: 4, item_value: 6 },
+///                      Item { item_weight: 7, item_value: 9 },
+///                      Item { item_weight: 3, item_value: 3 }],
+/// });
+/// // Greedy takes the first and last items, worth 9, then the first is swapped for the second.
+/// assert_eq!(approximate.as_solution.ks_value, 12);
+/// ```
+pub fn local_search_knapsack(problem: KnapsackProblem)
+    -> ApproximateSolution {
+    heuristic(problem, |problem, order| {
+        let (mut keep, _) = greedy_or_best_item(problem, order);
+        improve_by_swaps(problem, order, &mut keep);
+        keep
+    })
+}
@@ -0,0 +1,174 @@
+/*
+ * Branch and bound search for the knapsack problem.
+ *
+ * Items are considered in order of decreasing value per unit weight.
+ * At each node of the search we calculate the value we'd get if we were allowed to take a
+ * fraction of the remaining items (filling greedily by that ratio), which is an upper bound on
+ * anything we could possibly get below this node.
+ * If that bound can't beat the best solution found so far there's no point searching any
+ * further down this branch.
+ *
+ * The amount of work done depends on the items rather than on the capacity, so this is the right
+ * tool for a large capacity and a small number of items, where the dynamic programming solvers
+ * would need an enormous table.
+ */
+
+use std::cmp::Ordering;
+use super::{Item, KnapsackProblem, KnapsackSolution, solution_from_flags};
+use super::solver::SolverLimits;
+
+/// The `BranchStatistics` type -- a record of how much work a branch and bound search did.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BranchStatistics {
+    /// number of nodes in the search tree that were visited.
+    pub bs_nodes_visited: u64,
+    /// number of those nodes whose subtree was skipped because its upper bound couldn't beat
+    /// the best solution found at the time.
+    pub bs_nodes_pruned: u64,
+}
+
+/*
+ * Order items by decreasing value per unit weight, without going through floating point.
+ * An item with no weight has an infinite ratio.
+ * Items with no value go last: comparing crossed products would make one with no weight equal to
+ * everything, which isn't a consistent order, and sorting could then leave the rest out of order.
+ */
+pub(super) fn by_ratio(left: &Item, right: &Item) -> Ordering {
+    match (left.item_value == 0, right.item_value == 0) {
+        (true, true)  => return Ordering::Equal,
+        (true, false) => return Ordering::Greater,
+        (false, true) => return Ordering::Less,
+        (false, false) => (),
+    }
+    let left_cross  = left.item_value as u64 * right.item_weight as u64;
+    let right_cross = right.item_value as u64 * left.item_weight as u64;
+    right_cross.cmp(&left_cross)
+}
+
+/*
+ * Upper bound on the value reachable by taking `items` (sorted by ratio) into `capacity`, if we
+ * were allowed to take a fraction of an item.
+ * The fraction is rounded down, which is fine because every real solution has an integer value.
+ */
+pub(super) fn fractional_bound(items: &[Item], mut capacity: u64) -> u64 {
+    let mut bound = 0;
+    for item in items {
+        let weight = item.item_weight as u64;
+        if weight <= capacity {
+            capacity -= weight;
+            bound    += item.item_value as u64;
+        } else {
+            bound += item.item_value as u64 * capacity / weight;
+            break;
+        }
+    }
+    bound
+}
+
+struct BranchSearch<'a> {
+    items: &'a [Item],
+    taken: Vec<bool>,
+    best_value: u64,
+    best_taken: Vec<bool>,
+    statistics: BranchStatistics,
+    limits: &'a SolverLimits,
+    /* Whether the search was cut short by the limits, so the best solution might not be optimal. */
+    stopped: bool,
+}
+
+impl<'a> BranchSearch<'a> {
+    fn new(items: &'a [Item], limits: &'a SolverLimits) -> BranchSearch<'a> {
+        BranchSearch {
+            items,
+            taken: vec![false; items.len()],
+            best_value: 0,
+            best_taken: vec![false; items.len()],
+            statistics: BranchStatistics::default(),
+            limits,
+            stopped: false,
+        }
+    }
+
+    fn search(&mut self, index: usize, capacity: u64, value: u64) {
+        if self.stopped || self.limits.exhausted(self.statistics.bs_nodes_visited) {
+            self.stopped = true;
+            return;
+        }
+        self.statistics.bs_nodes_visited += 1;
+        if value > self.best_value {
+            self.best_value = value;
+            self.best_taken.copy_from_slice(&self.taken);
+        }
+
+        if index == self.items.len() {
+            return;
+        }
+        if value + fractional_bound(&self.items[index..], capacity) <= self.best_value {
+            self.statistics.bs_nodes_pruned += 1;
+            return;
+        }
+
+        /*
+         * Taking the item first means we follow the greedy solution straight down the tree,
+         * which gives a good lower bound to prune with as early as possible.
+         */
+        let item = &self.items[index];
+        if item.item_weight as u64 <= capacity {
+            self.taken[index] = true;
+            self.search(index + 1, capacity - item.item_weight as u64,
+                        value + item.item_value as u64);
+            self.taken[index] = false;
+        }
+        self.search(index + 1, capacity, value);
+    }
+}
+
+/// Returns an optimal solution to the `KnapsackProblem` using branch and bound, along with
+/// statistics about the search.
+///
+/// The running time depends on how well the fractional relaxation bounds the problem, it's
+/// exponential in the worst case but usually very fast, and it doesn't depend on the capacity.
+///
+/// ```
+/// use rust_algorithms::knapsack_problem::{branch_and_bound_knapsack, KnapsackProblem, KnapsackSolution, Item};
+///
+/// let (solution, statistics) = branch_and_bound_knapsack(KnapsackProblem {
+///     kp_capacity: 4_000_000_000,
+///     kp_options: vec![Item { item_weight: 3_000_000_000, item_value: 10 },
+///                      Item { item_weight: 2_000_000_000, item_value: 6 },
+///                      Item { item_weight: 2_000_000_000, item_value: 6 }],
+/// });
+/// assert_eq!(solution, KnapsackSolution {
+///     ks_weight:   4_000_000_000,
+///     ks_capacity: 0,
+///     ks_value:    12,
+///     ks_items:    vec![Item { item_weight: 2_000_000_000, item_value: 6 },
+///                       Item { item_weight: 2_000_000_000, item_value: 6 }],
+/// });
+/// assert!(statistics.bs_nodes_visited > 0);
+/// ```
+pub fn branch_and_bound_knapsack(problem: KnapsackProblem)
+    -> (KnapsackSolution, BranchStatistics) {
+    let (solution, statistics, _) = limited_branch_and_bound(problem, &SolverLimits::default());
+    (solution, statistics)
+}
+
+/*
+ * Branch and bound search that stops when `limits` are reached, returning the best solution found
+ * so far, the statistics, and whether the search finished (so the solution is optimal).
+ */
+pub(super) fn limited_branch_and_bound(problem: KnapsackProblem, limits: &SolverLimits)
+    -> (KnapsackSolution, BranchStatistics, bool) {
+    let mut order: Vec<usize> = (0..problem.kp_options.len()).collect();
+    order.sort_by(|&left, &right| by_ratio(&problem.kp_options[left], &problem.kp_options[right]));
+    let sorted: Vec<Item> = order.iter().map(|&index| problem.kp_options[index].clone()).collect();
+
+    let mut search = BranchSearch::new(&sorted, limits);
+    search.search(0, problem.kp_capacity as u64, 0);
+
+    let mut keep = vec![false; problem.kp_options.len()];
+    for (position, &index) in order.iter().enumerate() {
+        keep[index] = search.best_taken[position];
+    }
+    (solution_from_flags(problem, &keep), search.statistics, !search.stopped)
+}
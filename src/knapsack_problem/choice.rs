@@ -0,0 +1,100 @@
+/*
+ * The multiple-choice knapsack problem, where the items are split into groups and at most one
+ * item from each group can be taken (e.g. different sizes of the same server).
+ *
+ * This is the same dynamic programming as `dynamic_knapsack()`, except that each row of the table
+ * covers a whole group, and the best value for a capacity is found by trying each of the items in
+ * the group (or none of them).
+ */
+
+use super::Item;
+
+/// The `ChoiceProblem` type -- represents a multiple-choice knapsack problem.
+#[derive(Debug, Clone)]
+pub struct ChoiceProblem {
+    /// capacity of the knapsack -- how much the sum of Items.weight can reach.
+    pub cp_capacity: u32,
+    /// groups of mutually exclusive items, at most one of each group can be taken.
+    pub cp_groups: Vec<Vec<Item>>,
+}
+
+/// The `ChoiceSolution` type -- represents a filling of the knapsack with at most one item from
+/// each group.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChoiceSolution {
+    /// weight of the knapsack -- sum of the weights of the chosen items.
+    pub cs_weight: u32,
+    /// The remaining capacity in the knapsack.
+    pub cs_capacity: u32,
+    /// The value of this knapsack -- sum of the values of the chosen items.
+    pub cs_value: u32,
+    /// For each group in the problem, the index within that group of the chosen item, if any.
+    pub cs_choices: Vec<Option<usize>>,
+}
+
+/// Returns an optimal solution to the `ChoiceProblem`.
+///
+/// This takes O(capacity * n) time and O(capacity * groups) memory.
+/// When it doesn't matter whether an item is taken from a group, none is.
+///
+/// ```
+/// use rust_algorithms::knapsack_problem::{multiple_choice_knapsack, ChoiceProblem, ChoiceSolution, Item};
+///
+/// let servers = vec![Item { item_weight: 2, item_value: 3 },
+///                    Item { item_weight: 4, item_value: 7 },
+///                    Item { item_weight: 8, item_value: 10 }];
+/// let disks   = vec![Item { item_weight: 1, item_value: 1 },
+///                    Item { item_weight: 3, item_value: 4 }];
+/// assert_eq!(multiple_choice_knapsack(ChoiceProblem { cp_capacity: 8, cp_groups: vec![servers, disks] }),
+///            ChoiceSolution {
+///                cs_weight:   7,
+///                cs_capacity: 1,
+///                cs_value:    11,
+///                cs_choices:  vec![Some(1), Some(1)],
+///            });
+/// ```
+pub fn multiple_choice_knapsack(problem: ChoiceProblem)
+    -> ChoiceSolution {
+    let heaviest = problem.cp_groups.iter()
+        .map(|group| group.iter().map(|item| item.item_weight).max().unwrap_or(0))
+        .fold(0u32, |acc, weight| acc.saturating_add(weight));
+    let width = problem.cp_capacity.min(heaviest) as usize + 1;
+    let mut table = vec![0u32; (problem.cp_groups.len() + 1) * width];
+
+    for (index, group) in problem.cp_groups.iter().enumerate() {
+        let (previous, current) = table[index * width..(index + 2) * width].split_at_mut(width);
+        for (capacity, cell) in current.iter_mut().enumerate() {
+            *cell = group.iter()
+                .filter(|item| item.item_weight as usize <= capacity)
+                .map(|item| previous[capacity - item.item_weight as usize] + item.item_value)
+                .fold(previous[capacity], |best, value| best.max(value));
+        }
+    }
+
+    let mut remaining  = width - 1;
+    let mut cs_choices = vec![None; problem.cp_groups.len()];
+    for (index, group) in problem.cp_groups.iter().enumerate().rev() {
+        let previous = &table[index * width..(index + 1) * width];
+        let best     = table[(index + 1) * width + remaining];
+        if best > previous[remaining] {
+            let choice = group.iter()
+                .position(|item| item.item_weight as usize <= remaining &&
+                          previous[remaining - item.item_weight as usize] + item.item_value == best)
+                .expect("Dynamic programming table inconsistent");
+            remaining -= group[choice].item_weight as usize;
+            cs_choices[index] = Some(choice);
+        }
+    }
+
+    let chosen: Vec<&Item> = cs_choices.iter()
+        .zip(&problem.cp_groups)
+        .filter_map(|(choice, group)| choice.map(|index| &group[index]))
+        .collect();
+    let cs_weight = chosen.iter().map(|item| item.item_weight).sum();
+    ChoiceSolution {
+        cs_capacity : problem.cp_capacity - cs_weight,
+        cs_value    : chosen.iter().map(|item| item.item_value).sum(),
+        cs_weight,
+        cs_choices,
+    }
+}
@@ -0,0 +1,226 @@
+/*
+ * Knapsack problems with rules about which items can be taken together.
+ *
+ * A requirement `(a, b)` means item `a` can only be taken if item `b` is taken too, and a conflict
+ * `(a, b)` means items `a` and `b` can't both be taken.
+ *
+ * We first work out everything each item requires, directly or indirectly (its closure).
+ * Taking an item means taking its whole closure, and leaving an item out means leaving out
+ * everything that requires it.
+ * If the closure of an item contains two items that conflict then the rules contradict each other
+ * as far as that item is concerned, and we report it rather than quietly never choosing it.
+ *
+ * The search is branch and bound, using the fractional relaxation of the undecided items (with
+ * the rules ignored) as the upper bound.
+ */
+
+use super::{Item, KnapsackError, KnapsackProblem, KnapsackSolution, solution_from_flags};
+use super::branch_bound::by_ratio;
+
+/// The `KnapsackConstraints` type -- rules about which items can be taken together.
+///
+/// Items are referred to by their index in `kp_options`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KnapsackConstraints {
+    /// `(a, b)` means that item `a` can only be taken if item `b` is taken too.
+    pub kc_requires: Vec<(usize, usize)>,
+    /// `(a, b)` means that items `a` and `b` can't both be taken.
+    pub kc_conflicts: Vec<(usize, usize)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Undecided,
+    Taken,
+    Left,
+}
+
+struct ConstrainedSearch<'a> {
+    items: &'a [Item],
+    /* Every item each item requires, including itself. */
+    closures: Vec<Vec<usize>>,
+    /* Every item that requires each item, including itself. */
+    dependents: Vec<Vec<usize>>,
+    conflicts: Vec<Vec<usize>>,
+    ratio_order: Vec<usize>,
+    status: Vec<Status>,
+    best_value: u64,
+    best_status: Vec<Status>,
+}
+
+impl<'a> ConstrainedSearch<'a> {
+    fn bound(&self, capacity: u64) -> u64 {
+        let mut capacity = capacity;
+        let mut bound    = 0;
+        for &index in self.ratio_order.iter().filter(|&&index| self.status[index] == Status::Undecided) {
+            let item   = &self.items[index];
+            let weight = item.item_weight as u64;
+            if weight <= capacity {
+                capacity -= weight;
+                bound    += item.item_value as u64;
+            } else {
+                bound += item.item_value as u64 * capacity / weight;
+                break;
+            }
+        }
+        bound
+    }
+
+    /*
+     * Mark every undecided item in `indices` with `status`, returning the ones that changed so
+     * they can be put back afterwards.
+     */
+    fn mark(&mut self, indices: &[usize], status: Status) -> Vec<usize> {
+        let changed: Vec<usize> = indices.iter()
+            .cloned()
+            .filter(|&index| self.status[index] == Status::Undecided)
+            .collect();
+        for &index in &changed {
+            self.status[index] = status;
+        }
+        changed
+    }
+
+    fn unmark(&mut self, indices: &[usize]) {
+        for &index in indices {
+            self.status[index] = Status::Undecided;
+        }
+    }
+
+    fn can_take(&self, index: usize, capacity: u64) -> Option<(u64, u64)> {
+        let mut weight = 0;
+        let mut value  = 0;
+        for &required in &self.closures[index] {
+            match self.status[required] {
+                Status::Left => return None,
+                Status::Taken => continue,
+                Status::Undecided => {
+                    let in_conflict = self.conflicts[required].iter()
+                        .any(|&other| self.status[other] == Status::Taken);
+                    if in_conflict {
+                        return None;
+                    }
+                    weight += self.items[required].item_weight as u64;
+                    value  += self.items[required].item_value as u64;
+                },
+            }
+        }
+        if weight <= capacity { Some((weight, value)) } else { None }
+    }
+
+    fn search(&mut self, index: usize, capacity: u64, value: u64) {
+        if value > self.best_value {
+            self.best_value = value;
+            self.best_status.copy_from_slice(&self.status);
+        }
+
+        let index = match (index..self.items.len()).find(|&next| self.status[next] == Status::Undecided) {
+            Some(next) => next,
+            None => return,
+        };
+        if value + self.bound(capacity) <= self.best_value {
+            return;
+        }
+
+        if let Some((weight, gained)) = self.can_take(index, capacity) {
+            let closure = self.closures[index].clone();
+            let changed = self.mark(&closure, Status::Taken);
+            self.search(index + 1, capacity - weight, value + gained);
+            self.unmark(&changed);
+        }
+
+        let dependents = self.dependents[index].clone();
+        let changed = self.mark(&dependents, Status::Left);
+        self.search(index + 1, capacity, value);
+        self.unmark(&changed);
+    }
+}
+
+/* Everything reachable from `start` by following `edges`, including `start`. */
+fn reachable(edges: &[Vec<usize>], start: usize) -> Vec<usize> {
+    let mut seen  = vec![false; edges.len()];
+    let mut stack = vec![start];
+    let mut found = Vec::new();
+    seen[start] = true;
+    while let Some(current) = stack.pop() {
+        found.push(current);
+        for &next in &edges[current] {
+            if !seen[next] {
+                seen[next] = true;
+                stack.push(next);
+            }
+        }
+    }
+    found
+}
+
+/// Returns an optimal solution to the `KnapsackProblem` that keeps to the `constraints`.
+///
+/// Returns `KnapsackError::UnknownOption` if a constraint refers to an index that isn't in
+/// `kp_options`, and `KnapsackError::Contradiction` if there's an item that the constraints
+/// would never allow to be taken (because it requires two items that conflict).
+///
+/// ```
+/// use rust_algorithms::knapsack_problem::{constrained_knapsack, KnapsackConstraints, KnapsackProblem, Item};
+///
+/// let solution = constrained_knapsack(KnapsackProblem {
+///     kp_capacity: 11,
+///     kp_options: vec![Item { item_weight: 2, item_value: 10 },
+///                      Item { item_weight: 6, item_value: 1 },
+///                      Item { item_weight: 3, item_value: 8 },
+///                      Item { item_weight: 4, item_value: 6 }],
+/// }, &KnapsackConstraints {
+///     kc_requires: vec![(0, 1)],
+///     kc_conflicts: vec![(2, 3)],
+/// }).unwrap();
+/// assert_eq!(solution.ks_value, 19);
+/// assert_eq!(solution.ks_items, vec![Item { item_weight: 2, item_value: 10 },
+///                                    Item { item_weight: 6, item_value: 1 },
+///                                    Item { item_weight: 3, item_value: 8 }]);
+/// ```
+pub fn constrained_knapsack(problem: KnapsackProblem, constraints: &KnapsackConstraints)
+    -> Result<KnapsackSolution, KnapsackError> {
+    let count = problem.kp_options.len();
+    let mut requires    = vec![Vec::new(); count];
+    let mut required_by = vec![Vec::new(); count];
+    let mut conflicts   = vec![Vec::new(); count];
+    for &(left, right) in constraints.kc_requires.iter().chain(&constraints.kc_conflicts) {
+        if let Some(&index) = [left, right].iter().find(|&&index| index >= count) {
+            return Err(KnapsackError::UnknownOption(index));
+        }
+    }
+    for &(item, required) in &constraints.kc_requires {
+        requires[item].push(required);
+        required_by[required].push(item);
+    }
+    for &(left, right) in &constraints.kc_conflicts {
+        conflicts[left].push(right);
+        conflicts[right].push(left);
+    }
+
+    let closures: Vec<Vec<usize>> = (0..count).map(|index| reachable(&requires, index)).collect();
+    for (index, closure) in closures.iter().enumerate() {
+        let contradiction = closure.iter()
+            .any(|&member| conflicts[member].iter().any(|other| closure.contains(other)));
+        if contradiction {
+            return Err(KnapsackError::Contradiction(index));
+        }
+    }
+
+    let mut ratio_order: Vec<usize> = (0..count).collect();
+    ratio_order.sort_by(|&left, &right| by_ratio(&problem.kp_options[left], &problem.kp_options[right]));
+    let mut search = ConstrainedSearch {
+        items: &problem.kp_options,
+        dependents: (0..count).map(|index| reachable(&required_by, index)).collect(),
+        closures,
+        conflicts,
+        ratio_order,
+        status: vec![Status::Undecided; count],
+        best_value: 0,
+        best_status: vec![Status::Undecided; count],
+    };
+    search.search(0, problem.kp_capacity as u64, 0);
+
+    let keep: Vec<bool> = search.best_status.iter().map(|&status| status == Status::Taken).collect();
+    Ok(solution_from_flags(problem, &keep))
+}
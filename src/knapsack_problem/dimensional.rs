@@ -0,0 +1,212 @@
+/*
+ * The multi-dimensional knapsack problem, where each item uses several different resources
+ * (weight, volume, cost, ...) and the knapsack has a separate capacity for each of them.
+ *
+ * A dynamic programming table would need a dimension per resource, which gets out of hand very
+ * quickly, so this is solved with branch and bound.
+ * Dropping every constraint but one gives a normal knapsack problem, whose fractional relaxation
+ * bounds the value reachable from a node.
+ * We take the tightest of those bounds over all the resources.
+ */
+
+use std::cmp::Ordering;
+
+/// The `DimensionalItem` type -- represents one option to keep in a multi-dimensional knapsack.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DimensionalItem {
+    /// how much of each resource the item uses, in the same order as the capacities of the
+    /// problem.
+    pub di_usage: Vec<u32>,
+    /// value of the item, which decides our preference.
+    pub di_value: u32,
+}
+
+/// The `DimensionalProblem` type -- represents a multi-dimensional knapsack problem.
+#[derive(Debug, Clone)]
+pub struct DimensionalProblem {
+    /// capacity of the knapsack in each dimension.
+    pub dp_capacities: Vec<u32>,
+    /// options -- the list of items that we have available for carrying.
+    pub dp_options: Vec<DimensionalItem>,
+}
+
+/// The `DimensionalSolution` type -- represents a filling of a multi-dimensional knapsack.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DimensionalSolution {
+    /// how much of each resource is used -- the sum of DimensionalItem.usage.
+    pub ds_usage: Vec<u32>,
+    /// The remaining capacity in each dimension -- the capacities of the problem minus the usage.
+    pub ds_capacities: Vec<u32>,
+    /// The value of this knapsack -- the sum of items.value.
+    pub ds_value: u32,
+    /// The list of items stored in the knapsack, in the order they were given in the problem.
+    pub ds_items: Vec<DimensionalItem>,
+}
+
+/*
+ * Order items by decreasing value per unit of resource `dimension`, as `by_ratio()` does for
+ * plain items, and for the same reason items with no value go last.
+ */
+fn by_dimension_ratio(left: &DimensionalItem, right: &DimensionalItem, dimension: usize)
+    -> Ordering {
+    match (left.di_value == 0, right.di_value == 0) {
+        (true, true)  => return Ordering::Equal,
+        (true, false) => return Ordering::Greater,
+        (false, true) => return Ordering::Less,
+        (false, false) => (),
+    }
+    let left_cross  = left.di_value as u64 * right.di_usage[dimension] as u64;
+    let right_cross = right.di_value as u64 * left.di_usage[dimension] as u64;
+    right_cross.cmp(&left_cross)
+}
+
+struct DimensionalSearch<'a> {
+    items: &'a [DimensionalItem],
+    /* For each dimension, the positions in `items` sorted by value per unit of that resource. */
+    ratio_orders: Vec<Vec<usize>>,
+    taken: Vec<bool>,
+    best_value: u64,
+    best_taken: Vec<bool>,
+}
+
+impl<'a> DimensionalSearch<'a> {
+    fn new(items: &'a [DimensionalItem], dimensions: usize) -> DimensionalSearch<'a> {
+        let ratio_orders = (0..dimensions)
+            .map(|dimension| {
+                let mut order: Vec<usize> = (0..items.len()).collect();
+                order.sort_by(|&left, &right| by_dimension_ratio(&items[left], &items[right], dimension));
+                order
+            })
+            .collect();
+        DimensionalSearch {
+            items,
+            ratio_orders,
+            taken: vec![false; items.len()],
+            best_value: 0,
+            best_taken: vec![false; items.len()],
+        }
+    }
+
+    /*
+     * Fractional bound on the value of the items from `index` onwards, considering only the one
+     * resource `dimension`.
+     */
+    fn dimension_bound(&self, index: usize, dimension: usize, mut capacity: u64) -> u64 {
+        let mut bound = 0;
+        for &position in self.ratio_orders[dimension].iter().filter(|&&position| position >= index) {
+            let item  = &self.items[position];
+            let usage = item.di_usage[dimension] as u64;
+            if usage <= capacity {
+                capacity -= usage;
+                bound    += item.di_value as u64;
+            } else {
+                bound += item.di_value as u64 * capacity / usage;
+                break;
+            }
+        }
+        bound
+    }
+
+    fn search(&mut self, index: usize, capacities: &mut [u64], value: u64) {
+        if value > self.best_value {
+            self.best_value = value;
+            self.best_taken.copy_from_slice(&self.taken);
+        }
+        if index == self.items.len() {
+            return;
+        }
+
+        let bound = (0..capacities.len())
+            .map(|dimension| self.dimension_bound(index, dimension, capacities[dimension]))
+            .min()
+            .unwrap_or_else(|| self.items[index..].iter().map(|item| item.di_value as u64).sum());
+        if value + bound <= self.best_value {
+            return;
+        }
+
+        let item = &self.items[index];
+        if item.di_usage.iter().zip(capacities.iter()).all(|(&usage, &capacity)| usage as u64 <= capacity) {
+            for (capacity, &usage) in capacities.iter_mut().zip(&item.di_usage) {
+                *capacity -= usage as u64;
+            }
+            self.taken[index] = true;
+            self.search(index + 1, capacities, value + item.di_value as u64);
+            self.taken[index] = false;
+            for (capacity, &usage) in capacities.iter_mut().zip(&item.di_usage) {
+                *capacity += usage as u64;
+            }
+        }
+        self.search(index + 1, capacities, value);
+    }
+}
+
+/// Returns an optimal solution to the `DimensionalProblem`.
+///
+/// This is a branch and bound search, so is exponential in the worst case, but doesn't depend on
+/// the size of the capacities.
+///
+/// # Panics
+/// If any item doesn't have exactly one usage for each of the capacities.
+///
+/// ```
+/// use rust_algorithms::knapsack_problem::{dimensional_knapsack, DimensionalProblem, DimensionalItem};
+///
+/// let solution = dimensional_knapsack(DimensionalProblem {
+///     dp_capacities: vec![10, 5],
+///     dp_options: vec![DimensionalItem { di_usage: vec![9, 1], di_value: 10 },
+///                      DimensionalItem { di_usage: vec![5, 3], di_value: 6 },
+///                      DimensionalItem { di_usage: vec![4, 2], di_value: 5 }],
+/// });
+/// assert_eq!(solution.ds_value, 11);
+/// assert_eq!(solution.ds_usage, vec![9, 5]);
+/// assert_eq!(solution.ds_capacities, vec![1, 0]);
+/// ```
+pub fn dimensional_knapsack(problem: DimensionalProblem)
+    -> DimensionalSolution {
+    let dimensions = problem.dp_capacities.len();
+    if let Some(item) = problem.dp_options.iter().find(|item| item.di_usage.len() != dimensions) {
+        panic!("dimensional_knapsack() given item {:?} for a knapsack with {} dimensions",
+               item, dimensions);
+    }
+
+    /*
+     * Search in the order of value per unit of total resource usage, where each resource is
+     * measured as a proportion of the capacity for that resource.
+     * This is only a heuristic to find good solutions early, any order gives the right answer.
+     */
+    let density = |item: &DimensionalItem| -> f64 {
+        let usage: f64 = item.di_usage.iter().zip(&problem.dp_capacities)
+            .map(|(&usage, &capacity)| usage as f64 / (capacity as f64 + 1.0))
+            .sum();
+        item.di_value as f64 / (usage + f64::EPSILON)
+    };
+    let mut order: Vec<usize> = (0..problem.dp_options.len()).collect();
+    order.sort_by(|&left, &right| density(&problem.dp_options[right])
+                  .partial_cmp(&density(&problem.dp_options[left]))
+                  .unwrap_or(Ordering::Equal));
+    let sorted: Vec<DimensionalItem> = order.iter().map(|&index| problem.dp_options[index].clone()).collect();
+
+    let mut search = DimensionalSearch::new(&sorted, dimensions);
+    let mut capacities: Vec<u64> = problem.dp_capacities.iter().map(|&capacity| capacity as u64).collect();
+    search.search(0, &mut capacities, 0);
+
+    let mut keep = vec![false; problem.dp_options.len()];
+    for (position, &index) in order.iter().enumerate() {
+        keep[index] = search.best_taken[position];
+    }
+    let ds_items: Vec<DimensionalItem> = problem.dp_options.into_iter()
+        .zip(keep)
+        .filter_map(|(item, keep)| if keep { Some(item) } else { None })
+        .collect();
+    let ds_usage: Vec<u32> = (0..dimensions)
+        .map(|dimension| ds_items.iter().map(|item| item.di_usage[dimension]).sum())
+        .collect();
+    DimensionalSolution {
+        ds_capacities : problem.dp_capacities.iter().zip(&ds_usage)
+                            .map(|(&capacity, &usage)| capacity - usage)
+                            .collect(),
+        ds_value      : ds_items.iter().map(|item| item.di_value).sum(),
+        ds_usage,
+        ds_items,
+    }
+}
@@ -0,0 +1,333 @@
+/*
+ * Dynamic programming solutions to the knapsack problem.
+ *
+ * The table is indexed by (number of items considered, capacity), and holds the best value
+ * achievable with that many items and that much room.
+ * Filling it is O(n * capacity), which is pseudo-polynomial -- fine when the capacity is a
+ * reasonable size, hopeless when it's in the billions.
+ *
+ * We never need a column wider than the sum of all item weights, any capacity above that can
+ * carry everything, so the table is clamped to that width.
+ * This means a huge `kp_capacity` with a small set of items doesn't allocate a huge table.
+ */
+
+use super::{Item, KnapsackError, KnapsackProblem, KnapsackSolution, KnapsackValue, KnapsackWeight, solution_from_flags};
+
+/*
+ * How many columns back in the table taking `item` moves us.
+ * A weight that can't be used as an index is wider than any table we could allocate, so this
+ * saturates, and the item never fits.
+ */
+pub(super) fn column_weight<W: KnapsackWeight, V>(item: &Item<W, V>) -> usize {
+    item.item_weight.to_index().unwrap_or(usize::MAX)
+}
+
+/*
+ * Number of columns needed in a table row, see the note at the top of this file.
+ * The sum of the weights saturates, which is fine because this is only used to limit the width,
+ * if it's anywhere near `usize::MAX` then the capacity decides the width anyway.
+ */
+pub(super) fn table_width<W: KnapsackWeight, V>(problem: &KnapsackProblem<W, V>) -> usize {
+    let total_weight = problem.kp_options.iter()
+        .fold(0usize, |acc, item| acc.saturating_add(column_weight(item)));
+    let capacity = problem.kp_capacity.to_index().unwrap_or(usize::MAX);
+    capacity.min(total_weight) + 1
+}
+
+/*
+ * Choose the items to keep from a filled table by walking back from the last row, starting at
+ * column `capacity`.
+ *
+ * The decision made for each item mirrors the decision that `best_knapsack()` makes: take the
+ * item only if doing so is *strictly* better than leaving it out.
+ * This means both functions choose the same set of items when there are ties, and hence the
+ * results can be compared directly in the tests.
+ * The table must have been filled without overflowing, so every sum here was already made while
+ * filling it.
+ */
+pub(super) fn reconstruct<W, V>(table: &[V], width: usize, items: &[Item<W, V>], capacity: usize)
+    -> Vec<bool>
+    where W: KnapsackWeight, V: KnapsackValue {
+    let mut remaining = capacity;
+    let mut keep      = vec![false; items.len()];
+
+    for (row, item) in items.iter().enumerate().rev() {
+        let weight   = column_weight(item);
+        let previous = &table[row * width..(row + 1) * width];
+        let better = weight <= remaining && match previous[remaining - weight].checked_add(item.item_value) {
+            Some(with_item) => with_item > previous[remaining],
+            None => false,
+        };
+        if better {
+            remaining -= weight;
+            keep[row]  = true;
+        }
+    }
+    keep
+}
+
+/*
+ * Fill the cells of a row of the table for the columns starting at `start`, given the whole of
+ * the previous row, where the row adds an item weighing `weight` columns and worth `value`.
+ *
+ * Returns `None` if a value overflows.
+ * Every cell is the value of some set of items that fits in the knapsack, so when that happens
+ * the best value of the whole problem overflows too.
+ */
+pub(super) fn fill_columns<V: KnapsackValue>(previous: &[V], current: &mut [V], start: usize, weight: usize, value: V)
+    -> Option<()> {
+    for (offset, cell) in current.iter_mut().enumerate() {
+        let capacity = start + offset;
+        *cell = previous[capacity];
+        if weight <= capacity {
+            let with_item = previous[capacity - weight].checked_add(value)?;
+            if with_item > *cell {
+                *cell = with_item;
+            }
+        }
+    }
+    Some(())
+}
+
+/* Fill the whole table for `items`, with `width` columns. */
+fn fill_table<W, V>(items: &[Item<W, V>], width: usize)
+    -> Result<Vec<V>, KnapsackError>
+    where W: KnapsackWeight, V: KnapsackValue {
+    let mut table = vec![V::zero(); (items.len() + 1) * width];
+
+    for (index, item) in items.iter().enumerate() {
+        let (previous, current) = table[index * width..(index + 2) * width].split_at_mut(width);
+        fill_columns(previous, current, 0, column_weight(item), item.item_value)
+            .ok_or(KnapsackError::ValueOverflow(index))?;
+    }
+    Ok(table)
+}
+
+/*
+ * Which of `problem.kp_options` `dynamic_knapsack()` would keep.
+ * Split out so that other solvers can reduce their problem to a plain knapsack problem and map
+ * the chosen items back to their own.
+ */
+pub(super) fn dynamic_choice<W, V>(problem: &KnapsackProblem<W, V>)
+    -> Result<Vec<bool>, KnapsackError>
+    where W: KnapsackWeight, V: KnapsackValue {
+    let width = table_width(problem);
+    let table = fill_table(&problem.kp_options, width)?;
+    Ok(reconstruct(&table, width, &problem.kp_options, width - 1))
+}
+
+/// Returns an optimal solution to the `KnapsackProblem` using dynamic programming.
+///
+/// This takes O(n * capacity) time and memory, so is much faster than `best_knapsack()` for
+/// anything but the smallest number of items, provided the capacity (or the total weight of the
+/// items) is a manageable size.
+///
+/// When there are multiple optimal solutions, the one chosen is the same one that
+/// `best_knapsack()` would choose.
+///
+/// # Panics
+/// If the value of the best knapsack doesn't fit in the value type, use `try_dynamic_knapsack()`
+/// to handle that case.
+///
+/// ```
+/// use rust_algorithms::knapsack_problem::{dynamic_knapsack, KnapsackProblem, KnapsackSolution, Item};
+///
+/// assert_eq!(dynamic_knapsack( KnapsackProblem {
+///                                  kp_capacity: 5,
+///                                  kp_options: vec![Item { item_weight: 3, item_value: 4 },
+///                                                   Item { item_weight: 4, item_value: 5 },
+///                                                   Item { item_weight: 2, item_value: 3 }],
+///                              } ),
+///             KnapsackSolution {
+///                 ks_weight:   5,
+///                 ks_capacity: 0,
+///                 ks_value:    7,
+///                 ks_items:    vec![Item { item_weight: 3, item_value: 4 },
+///                                   Item { item_weight: 2, item_value: 3 }],
+///             });
+/// ```
+pub fn dynamic_knapsack<W, V>(problem: KnapsackProblem<W, V>)
+    -> KnapsackSolution<W, V>
+    where W: KnapsackWeight, V: KnapsackValue {
+    match try_dynamic_knapsack(problem) {
+        Ok(solution) => solution,
+        Err(error) => panic!("dynamic_knapsack() failed: {}", error),
+    }
+}
+
+/// Returns an optimal solution to the `KnapsackProblem` using dynamic programming, or an error if
+/// its value would overflow.
+///
+/// This returns `KnapsackError::ValueOverflow` in exactly the same cases as
+/// `try_best_knapsack()`, though the item it names may differ.
+///
+/// ```
+/// use rust_algorithms::knapsack_problem::{try_dynamic_knapsack, KnapsackProblem, KnapsackError, Item};
+///
+/// assert_eq!(try_dynamic_knapsack( KnapsackProblem {
+///                                      kp_capacity: 3,
+///                                      kp_options: vec![Item { item_weight: 1, item_value: u32::MAX },
+///                                                       Item { item_weight: 1, item_value: 1 }],
+///                                  } ),
+///             Err(KnapsackError::ValueOverflow(1)));
+/// ```
+pub fn try_dynamic_knapsack<W, V>(problem: KnapsackProblem<W, V>)
+    -> Result<KnapsackSolution<W, V>, KnapsackError>
+    where W: KnapsackWeight, V: KnapsackValue {
+    let keep = dynamic_choice(&problem)?;
+    Ok(solution_from_flags(problem, &keep))
+}
+
+/// Returns an optimal solution for each of `capacities`, all choosing from the same `options`.
+///
+/// This fills a single table wide enough for the largest capacity (or the total weight of the
+/// options, if that's smaller), and reads every solution from it, so it takes
+/// O(n * max(capacities) + n * capacities.len()) time rather than solving each problem separately.
+/// Each solution is the same as `dynamic_knapsack()` would return for that capacity.
+///
+/// # Panics
+/// If the value of the best knapsack for the largest capacity doesn't fit in the value type.
+///
+/// ```
+/// use rust_algorithms::knapsack_problem::{batch_knapsack, Item};
+///
+/// let options = vec![Item { item_weight: 3, item_value: 4 },
+///                    Item { item_weight: 4, item_value: 5 },
+///                    Item { item_weight: 2, item_value: 3 }];
+/// let values: Vec<u32> = batch_knapsack(options, &[0, 2, 5, 100])
+///                            .into_iter()
+///                            .map(|solution| solution.ks_value)
+///                            .collect();
+/// assert_eq!(values, vec![0, 3, 7, 12]);
+/// ```
+pub fn batch_knapsack<W, V>(options: Vec<Item<W, V>>, capacities: &[W])
+    -> Vec<KnapsackSolution<W, V>>
+    where W: KnapsackWeight, V: KnapsackValue {
+    let largest = capacities.iter().cloned().max().unwrap_or_else(W::zero);
+    let problem = KnapsackProblem { kp_capacity: largest, kp_options: options };
+    let width   = table_width(&problem);
+    let table   = match fill_table(&problem.kp_options, width) {
+        Ok(table) => table,
+        Err(error) => panic!("batch_knapsack() failed: {}", error),
+    };
+
+    capacities.iter()
+        .map(|&capacity| {
+            let column = capacity.to_index().unwrap_or(usize::MAX).min(width - 1);
+            let keep   = reconstruct(&table, width, &problem.kp_options, column);
+            solution_from_flags(KnapsackProblem { kp_capacity: capacity, kp_options: problem.kp_options.clone() }, &keep)
+        })
+        .collect()
+}
+
+/*
+ * Fold `items` into a table row.
+ * `row[c]` starts as the best value using some prefix of the items with capacity `c`, and ends as
+ * the best value using that prefix followed by `items`.
+ * Going from the top capacity downwards means each item is only counted once.
+ * `first` is the index into the options of `items[0]`, to name the item in an overflow.
+ */
+fn extend_row<W, V>(row: &[V], items: &[Item<W, V>], first: usize)
+    -> Result<Vec<V>, KnapsackError>
+    where W: KnapsackWeight, V: KnapsackValue {
+    let mut retval = row.to_vec();
+    for (index, item) in items.iter().enumerate() {
+        let weight = column_weight(item);
+        for capacity in (weight..retval.len()).rev() {
+            let with_item = retval[capacity - weight].checked_add(item.item_value)
+                .ok_or(KnapsackError::ValueOverflow(first + index))?;
+            if with_item > retval[capacity] {
+                retval[capacity] = with_item;
+            }
+        }
+    }
+    Ok(retval)
+}
+
+/*
+ * Mark which of `items` to keep in `chosen`, returning the capacity left for the items before
+ * them.
+ *
+ * `base` is the row of the table for all items before `items`, and `capacity` is how much room
+ * there is for those items and `items` together.
+ * `first` is the index into the options of `items[0]`, as for `extend_row()`.
+ *
+ * `best_knapsack()` decides on the last item first, so we have to do the same.
+ * The decisions for the top half of `items` only need the row of the table at the midpoint,
+ * which we can calculate from `base` in O(capacity) memory.
+ * Once those are made we know how much capacity is left for the bottom half, and can throw the
+ * midpoint row away before recursing on the bottom half with the original `base`.
+ */
+fn choose_items<W, V>(items: &[Item<W, V>], first: usize, base: &[V], capacity: usize, chosen: &mut [bool])
+    -> Result<usize, KnapsackError>
+    where W: KnapsackWeight, V: KnapsackValue {
+    match items.len() {
+        0 => Ok(capacity),
+        1 => {
+            let weight = column_weight(&items[0]);
+            if weight > capacity {
+                return Ok(capacity);
+            }
+            let with_item = base[capacity - weight].checked_add(items[0].item_value)
+                .ok_or(KnapsackError::ValueOverflow(first))?;
+            if with_item > base[capacity] {
+                chosen[0] = true;
+                Ok(capacity - weight)
+            } else {
+                Ok(capacity)
+            }
+        },
+        length => {
+            let middle = length / 2;
+            let remaining = {
+                let middle_row = extend_row(base, &items[..middle], first)?;
+                choose_items(&items[middle..], first + middle, &middle_row, capacity, &mut chosen[middle..])?
+            };
+            choose_items(&items[..middle], first, base, remaining, &mut chosen[..middle])
+        },
+    }
+}
+
+/// Returns an optimal solution to the `KnapsackProblem` using dynamic programming, without
+/// keeping the whole table.
+///
+/// `dynamic_knapsack()` keeps the entire n * capacity table around so it can work out which items
+/// were chosen.
+/// This function only ever keeps one row of the table per level of a divide-and-conquer search
+/// (in the style of Hirschberg's algorithm), so it needs O(capacity * log(n)) memory at the cost
+/// of O(n * log(n) * capacity) time.
+///
+/// It chooses the same items as `best_knapsack()` when there are multiple optimal solutions.
+/// That is what costs the `log(n)`: the choice for each item depends on the row of the table
+/// before it, so the row a half starts from has to be kept while the half above it is decided.
+///
+/// # Panics
+/// If the value of the best knapsack doesn't fit in the value type.
+///
+/// ```
+/// use rust_algorithms::knapsack_problem::{hirschberg_knapsack, KnapsackProblem, KnapsackSolution, Item};
+///
+/// assert_eq!(hirschberg_knapsack( KnapsackProblem {
+///                                     kp_capacity: 5,
+///                                     kp_options: vec![Item { item_weight: 3, item_value: 4 },
+///                                                      Item { item_weight: 4, item_value: 5 },
+///                                                      Item { item_weight: 2, item_value: 3 }],
+///                                 } ),
+///             KnapsackSolution {
+///                 ks_weight:   5,
+///                 ks_capacity: 0,
+///                 ks_value:    7,
+///                 ks_items:    vec![Item { item_weight: 3, item_value: 4 },
+///                                   Item { item_weight: 2, item_value: 3 }],
+///             });
+/// ```
+pub fn hirschberg_knapsack<W, V>(problem: KnapsackProblem<W, V>)
+    -> KnapsackSolution<W, V>
+    where W: KnapsackWeight, V: KnapsackValue {
+    let width     = table_width(&problem);
+    let mut flags = vec![false; problem.kp_options.len()];
+    if let Err(error) = choose_items(&problem.kp_options, 0, &vec![V::zero(); width], width - 1, &mut flags) {
+        panic!("hirschberg_knapsack() failed: {}", error);
+    }
+    solution_from_flags(problem, &flags)
+}
@@ -0,0 +1,338 @@
+/*
+ * Reading and writing knapsack problems and solutions as text.
+ *
+ * Our own format is one line with the capacity, then one line per item with its weight and its
+ * value separated by whitespace.
+ * Blank lines and anything after a `#` are ignored.
+ * A solution is written the same way, with the capacity of the knapsack it fills and the items
+ * that were taken, so a solution can also be read as the problem of rearranging its own items.
+ *
+ * We can also read two layouts used for published benchmark instances:
+ * - Pisinger's instances (`knapPI_*.csv`), where each instance is a name line, `n`, `c`, `z` and
+ *   `time` lines, one `index,value,weight,taken` line per item, and a line of dashes.
+ * - OR-Library's multi-dimensional instances (`mknap*.txt`), which are just whitespace separated
+ *   numbers: the number of problems, then for each problem the number of items, the number of
+ *   dimensions and the optimal value, the values of the items, the usage of the items for each
+ *   dimension in turn, and the capacities.
+ */
+
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+use std::str::FromStr;
+use super::{DimensionalItem, DimensionalProblem, Item, KnapsackProblem, KnapsackSolution,
+            KnapsackValue, KnapsackWeight};
+
+/// The `ParseError` type -- the reason some text couldn't be read, and where.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The line the problem is on, counting from one.
+    /// When the text ends too soon this is one past the last line.
+    pub pe_line: usize,
+    /// What was wrong with that line.
+    pub pe_message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.pe_line, self.pe_message)
+    }
+}
+
+impl Error for ParseError {}
+
+/// The `PisingerInstance` type -- one instance from one of Pisinger's benchmark files.
+///
+/// Some of the instances have coefficients too large for `u32`, those can be read with `u64`
+/// weights and values.
+#[derive(Debug, Clone)]
+pub struct PisingerInstance<W = u32, V = u32> {
+    /// The name of the instance, e.g. `knapPI_1_50_1000_1`.
+    pub pi_name: String,
+    /// The problem itself.
+    pub pi_problem: KnapsackProblem<W, V>,
+    /// The optimal value, as given in the file.
+    pub pi_optimum: V,
+    /// Whether each item of `pi_problem.kp_options` is taken in the optimal solution given in
+    /// the file.
+    pub pi_taken: Vec<bool>,
+}
+
+/// The `OrLibraryInstance` type -- one problem from one of OR-Library's multi-dimensional
+/// knapsack files.
+#[derive(Debug, Clone)]
+pub struct OrLibraryInstance {
+    /// The problem itself.
+    pub oi_problem: DimensionalProblem,
+    /// The optimal value, as given in the file (some files give zero when it isn't known).
+    pub oi_optimum: u32,
+}
+
+fn parse_error<T>(line: usize, message: String) -> Result<T, ParseError> {
+    Err(ParseError { pe_line: line, pe_message: message })
+}
+
+fn parse_number<T: FromStr>(line: usize, text: &str) -> Result<T, ParseError> {
+    match text.parse() {
+        Ok(number) => Ok(number),
+        Err(_) => parse_error(line, format!("'{}' is not a valid number", text)),
+    }
+}
+
+/* The lines with something on them, without comments, and numbered from one. */
+fn content_lines(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.split('#').next().unwrap_or("").trim()))
+        .filter(|&(_, line)| !line.is_empty())
+}
+
+fn end_line(text: &str) -> usize {
+    text.lines().count() + 1
+}
+
+/* Read our own format, returning the capacity and the items. */
+fn read_items<W, V>(text: &str) -> Result<(W, Vec<Item<W, V>>), ParseError>
+    where W: FromStr, V: FromStr {
+    let mut lines = content_lines(text);
+    let capacity = match lines.next() {
+        Some((line, content)) => parse_number(line, content)?,
+        None => return parse_error(end_line(text), "expected the capacity".to_string()),
+    };
+    let mut items = Vec::new();
+    for (line, content) in lines {
+        let fields: Vec<&str> = content.split_whitespace().collect();
+        if fields.len() != 2 {
+            return parse_error(line, format!("expected a weight and a value, found {} fields", fields.len()));
+        }
+        items.push(Item { item_weight: parse_number(line, fields[0])?, item_value: parse_number(line, fields[1])? });
+    }
+    Ok((capacity, items))
+}
+
+fn write_items<W: Display, V: Display>(capacity: &W, items: &[Item<W, V>]) -> String {
+    let mut retval = format!("{}\n", capacity);
+    for item in items {
+        retval.push_str(&format!("{} {}\n", item.item_weight, item.item_value));
+    }
+    retval
+}
+
+/// Read a `KnapsackProblem` in our own format, see `write_problem()`.
+///
+/// ```
+/// use rust_algorithms::knapsack_problem::{read_problem, KnapsackProblem, Item};
+///
+/// let problem: KnapsackProblem = read_problem("# capacity\n5\n3 4\n4 5\n").unwrap();
+/// assert_eq!(problem.kp_capacity, 5);
+/// assert_eq!(problem.kp_options, vec![Item { item_weight: 3, item_value: 4 },
+///                                     Item { item_weight: 4, item_value: 5 }]);
+///
+/// let error = read_problem::<u32, u32>("5\n3 4\n4\n").unwrap_err();
+/// assert_eq!(error.to_string(), "line 3: expected a weight and a value, found 1 fields");
+/// ```
+pub fn read_problem<W, V>(text: &str)
+    -> Result<KnapsackProblem<W, V>, ParseError>
+    where W: KnapsackWeight + FromStr, V: KnapsackValue + FromStr {
+    let (kp_capacity, kp_options) = read_items(text)?;
+    Ok(KnapsackProblem { kp_capacity, kp_options })
+}
+
+/// Write a `KnapsackProblem` in our own format: a line with the capacity, then a line with the
+/// weight and value of each item.
+///
+/// ```
+/// use rust_algorithms::knapsack_problem::{write_problem, KnapsackProblem, Item};
+///
+/// let text = write_problem(&KnapsackProblem {
+///     kp_capacity: 5,
+///     kp_options: vec![Item { item_weight: 3, item_value: 4 },
+///                      Item { item_weight: 4, item_value: 5 }],
+/// });
+/// assert_eq!(text, "5\n3 4\n4 5\n");
+/// ```
+pub fn write_problem<W, V>(problem: &KnapsackProblem<W, V>)
+    -> String
+    where W: KnapsackWeight + Display, V: KnapsackValue + Display {
+    write_items(&problem.kp_capacity, &problem.kp_options)
+}
+
+/// Read a `KnapsackSolution` in our own format, see `write_solution()`.
+///
+/// Returns an error on the line of the first item that doesn't fit in what the items before it
+/// left of the capacity, or whose value takes the total past what `V` can hold.
+pub fn read_solution<W, V>(text: &str)
+    -> Result<KnapsackSolution<W, V>, ParseError>
+    where W: KnapsackWeight + FromStr, V: KnapsackValue + FromStr {
+    let (capacity, ks_items): (W, Vec<Item<W, V>>) = read_items(text)?;
+    /* Each item is on the next line with something on it after the capacity. */
+    let item_lines = content_lines(text).skip(1).map(|(line, _)| line);
+    let mut ks_weight = W::zero();
+    let mut ks_value  = V::zero();
+    for (item, line) in ks_items.iter().zip(item_lines) {
+        /* Compare with what is left, so a huge weight can't overflow the total. */
+        if item.item_weight > capacity - ks_weight {
+            return parse_error(line, format!("items weigh more than the capacity {:?}", capacity));
+        }
+        ks_weight = ks_weight + item.item_weight;
+        ks_value = match ks_value.checked_add(item.item_value) {
+            Some(value) => value,
+            None => return parse_error(line, "the total value of the items is too large".to_string()),
+        };
+    }
+    Ok(KnapsackSolution {
+        ks_capacity : capacity - ks_weight,
+        ks_weight,
+        ks_value,
+        ks_items,
+    })
+}
+
+/// Write a `KnapsackSolution` in our own format: a line with the capacity of the whole
+/// knapsack, then a line with the weight and value of each item taken.
+///
+/// ```
+/// use rust_algorithms::knapsack_problem::{read_solution, write_solution, dynamic_knapsack, KnapsackProblem, Item};
+///
+/// let solution = dynamic_knapsack(KnapsackProblem {
+///     kp_capacity: 6,
+///     kp_options: vec![Item { item_weight: 3, item_value: 4 },
+///                      Item { item_weight: 4, item_value: 5 },
+///                      Item { item_weight: 2, item_value: 3 }],
+/// });
+/// let text = write_solution(&solution);
+/// assert_eq!(text, "6\n4 5\n2 3\n");
+/// assert_eq!(read_solution(&text), Ok(solution));
+/// ```
+pub fn write_solution<W, V>(solution: &KnapsackSolution<W, V>)
+    -> String
+    where W: KnapsackWeight + Display, V: KnapsackValue + Display {
+    write_items(&(solution.ks_weight + solution.ks_capacity), &solution.ks_items)
+}
+
+/* Read a line of Pisinger's format that should be `<key> <number>`. */
+fn pisinger_field<T: FromStr>(line: Option<(usize, &str)>, key: &str, end: usize) -> Result<T, ParseError> {
+    let (line, content) = match line {
+        Some(found) => found,
+        None => return parse_error(end, format!("expected the '{}' line", key)),
+    };
+    let mut fields = content.split_whitespace();
+    if fields.next() != Some(key) {
+        return parse_error(line, format!("expected the '{}' line", key));
+    }
+    match fields.next() {
+        Some(number) => parse_number(line, number),
+        None => parse_error(line, format!("expected a number after '{}'", key)),
+    }
+}
+
+/// Read every instance from one of Pisinger's benchmark files.
+///
+/// ```
+/// use rust_algorithms::knapsack_problem::{read_pisinger, Item};
+///
+/// let instances = read_pisinger::<u32, u32>("knapPI_1_2_1000_1\nn 2\nc 5\nz 4\ntime 0.00\n\
+///                                            1,4,3,1\n2,5,6,0\n-----\n\n").unwrap();
+/// assert_eq!(instances.len(), 1);
+/// assert_eq!(instances[0].pi_name, "knapPI_1_2_1000_1");
+/// assert_eq!(instances[0].pi_problem.kp_options[1], Item { item_weight: 6, item_value: 5 });
+/// assert_eq!(instances[0].pi_taken, vec![true, false]);
+/// ```
+pub fn read_pisinger<W, V>(text: &str)
+    -> Result<Vec<PisingerInstance<W, V>>, ParseError>
+    where W: KnapsackWeight + FromStr, V: KnapsackValue + FromStr {
+    let end = end_line(text);
+    let mut lines = content_lines(text);
+    let mut instances = Vec::new();
+    while let Some((_, name)) = lines.next() {
+        let count: u32  = pisinger_field(lines.next(), "n", end)?;
+        let kp_capacity = pisinger_field(lines.next(), "c", end)?;
+        let pi_optimum  = pisinger_field(lines.next(), "z", end)?;
+        match lines.next() {
+            Some((_, content)) if content.starts_with("time") => (),
+            Some((line, _)) => return parse_error(line, "expected the 'time' line".to_string()),
+            None => return parse_error(end, "expected the 'time' line".to_string()),
+        }
+
+        let mut kp_options = Vec::new();
+        let mut pi_taken   = Vec::new();
+        for expected in 1..=count {
+            let (line, content) = match lines.next() {
+                Some(found) => found,
+                None => return parse_error(end, format!("expected item {} of {}", expected, count)),
+            };
+            let fields: Vec<&str> = content.split(',').map(|field| field.trim()).collect();
+            if fields.len() != 4 {
+                return parse_error(line, format!("expected index, value, weight and taken, found {} fields", fields.len()));
+            }
+            if parse_number::<u32>(line, fields[0])? != expected {
+                return parse_error(line, format!("expected item {}, found item {}", expected, fields[0]));
+            }
+            kp_options.push(Item { item_value: parse_number(line, fields[1])?, item_weight: parse_number(line, fields[2])? });
+            pi_taken.push(match fields[3] {
+                "0" => false,
+                "1" => true,
+                other => return parse_error(line, format!("expected 0 or 1 for taken, found '{}'", other)),
+            });
+        }
+
+        match lines.next() {
+            Some((_, content)) if content.chars().all(|character| character == '-') => (),
+            Some((line, _)) => return parse_error(line, format!("expected the end of the instance after {} items", count)),
+            None => return parse_error(end, "expected the end of the instance".to_string()),
+        }
+        instances.push(PisingerInstance {
+            pi_name: name.to_string(),
+            pi_problem: KnapsackProblem { kp_capacity, kp_options },
+            pi_optimum,
+            pi_taken,
+        });
+    }
+    Ok(instances)
+}
+
+/// Read every problem from one of OR-Library's multi-dimensional knapsack files.
+///
+/// ```
+/// use rust_algorithms::knapsack_problem::{read_or_library, DimensionalItem};
+///
+/// let instances = read_or_library(" 1\n 3 2 9\n 4 5 3\n 3 4 2\n 1 2 3\n 5 4\n").unwrap();
+/// assert_eq!(instances[0].oi_optimum, 9);
+/// assert_eq!(instances[0].oi_problem.dp_capacities, vec![5, 4]);
+/// assert_eq!(instances[0].oi_problem.dp_options[0], DimensionalItem { di_usage: vec![3, 1], di_value: 4 });
+/// ```
+pub fn read_or_library(text: &str)
+    -> Result<Vec<OrLibraryInstance>, ParseError> {
+    let end = end_line(text);
+    let mut tokens = content_lines(text)
+        .flat_map(|(line, content)| content.split_whitespace().map(move |token| (line, token)));
+    let mut next_number = |what: &str| -> Result<u32, ParseError> {
+        match tokens.next() {
+            Some((line, token)) => parse_number(line, token),
+            None => parse_error(end, format!("expected {}", what)),
+        }
+    };
+
+    let problems = next_number("the number of problems")?;
+    let mut instances = Vec::new();
+    for _ in 0..problems {
+        let count      = next_number("the number of items")? as usize;
+        let dimensions = next_number("the number of dimensions")? as usize;
+        let oi_optimum = next_number("the optimal value")?;
+        /* The counts come from the file, so the vectors grow as the numbers are read. */
+        let mut dp_options = Vec::new();
+        for _ in 0..count {
+            dp_options.push(DimensionalItem { di_usage: Vec::new(), di_value: next_number("an item value")? });
+        }
+        for _ in 0..dimensions {
+            for item in &mut dp_options {
+                item.di_usage.push(next_number("an item usage")?);
+            }
+        }
+        let dp_capacities = (0..dimensions)
+            .map(|_| next_number("a capacity"))
+            .collect::<Result<Vec<u32>, ParseError>>()?;
+        instances.push(OrLibraryInstance { oi_problem: DimensionalProblem { dp_capacities, dp_options }, oi_optimum });
+    }
+    Ok(instances)
+}
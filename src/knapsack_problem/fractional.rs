@@ -0,0 +1,85 @@
+/*
+ * The fractional (or continuous) knapsack problem, where any fraction of an item can be taken.
+ *
+ * This is solved exactly by the greedy algorithm: take items in order of decreasing value per
+ * unit weight until the next one doesn't fit, then take as much of that one as will fit.
+ * At most one item is ever only partially taken.
+ */
+
+use super::{Item, KnapsackProblem};
+use super::branch_bound::by_ratio;
+
+/// The `FractionalItem` type -- represents how much of one `Item` is taken.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FractionalItem {
+    /// the item that some of is being taken.
+    pub fi_item: Item,
+    /// how much of the item is taken, greater than 0 and at most 1.
+    pub fi_fraction: f64,
+}
+
+/// The `FractionalSolution` type -- represents a filling of the knapsack with divisible items.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FractionalSolution {
+    /// weight of the knapsack -- sum of Item.weight times the fraction taken.
+    /// This is always a whole number as the only partially taken item fills the knapsack.
+    pub fs_weight: u32,
+    /// The remaining capacity in the knapsack.
+    pub fs_capacity: u32,
+    /// The value of this knapsack -- the sum of Item.value times the fraction taken.
+    pub fs_value: f64,
+    /// The items stored in the knapsack, in the order they were chosen (by decreasing value per
+    /// unit weight).
+    pub fs_items: Vec<FractionalItem>,
+}
+
+/// Returns the optimal filling of the knapsack when any fraction of an `Item` can be taken.
+///
+/// Items with no value are never taken.
+///
+/// ```
+/// use rust_algorithms::knapsack_problem::{fractional_knapsack, KnapsackProblem, Item};
+///
+/// let solution = fractional_knapsack(KnapsackProblem {
+///     kp_capacity: 5,
+///     kp_options: vec![Item { item_weight: 4, item_value: 2 },
+///                      Item { item_weight: 3, item_value: 6 }],
+/// });
+/// assert_eq!(solution.fs_weight, 5);
+/// assert_eq!(solution.fs_capacity, 0);
+/// assert_eq!(solution.fs_value, 7.0);
+/// assert_eq!(solution.fs_items[0].fi_fraction, 1.0);
+/// assert_eq!(solution.fs_items[1].fi_fraction, 0.5);
+/// ```
+pub fn fractional_knapsack(mut problem: KnapsackProblem)
+    -> FractionalSolution {
+    problem.kp_options.retain(|item| item.item_value != 0);
+    problem.kp_options.sort_by(by_ratio);
+
+    let mut remaining = problem.kp_capacity;
+    let mut fs_value  = 0.0;
+    let mut fs_items  = Vec::new();
+    for item in problem.kp_options {
+        if remaining == 0 && item.item_weight != 0 {
+            break;
+        }
+        let fi_fraction =
+            if item.item_weight <= remaining {
+                remaining -= item.item_weight;
+                1.0
+            } else {
+                let fraction = remaining as f64 / item.item_weight as f64;
+                remaining = 0;
+                fraction
+            };
+        fs_value += item.item_value as f64 * fi_fraction;
+        fs_items.push(FractionalItem { fi_item: item, fi_fraction });
+    }
+
+    FractionalSolution {
+        fs_weight   : problem.kp_capacity - remaining,
+        fs_capacity : remaining,
+        fs_value,
+        fs_items,
+    }
+}
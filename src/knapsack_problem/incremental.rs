@@ -0,0 +1,155 @@
+/*
+ * A knapsack problem that changes a little at a time, keeping the dynamic programming table of
+ * `dynamic_knapsack()` up to date rather than refilling it for every change.
+ *
+ * Each row of the table only depends on the row before it, so:
+ * - adding an item to the end only needs one new row,
+ * - removing an item only needs the rows after it recalculating,
+ * - a column only depends on columns to its left, so making the table wider only needs the new
+ *   columns filling.
+ *
+ * The table needs to be `min(capacity, total weight) + 1` columns wide.
+ * When it has to grow we at least double its width (without going past the capacity), so that a
+ * run of additions doesn't keep copying the table.
+ * It never shrinks, extra columns on the right don't change the solution.
+ */
+
+use super::{Item, KnapsackError, KnapsackProblem, KnapsackSolution, KnapsackValue, KnapsackWeight, solution_from_flags};
+use super::dynamic::{column_weight, fill_columns, reconstruct, table_width};
+
+/// The `IncrementalKnapsack` type -- a knapsack problem that can be changed one item at a time,
+/// whose optimal solution is kept cheap to find.
+///
+/// The solutions are the same as `dynamic_knapsack()` would return for the current problem.
+///
+/// ```
+/// use rust_algorithms::knapsack_problem::{IncrementalKnapsack, KnapsackProblem, Item};
+///
+/// let mut knapsack = IncrementalKnapsack::new(KnapsackProblem {
+///     kp_capacity: 5,
+///     kp_options: vec![Item { item_weight: 3, item_value: 4 },
+///                      Item { item_weight: 4, item_value: 5 }],
+/// });
+/// assert_eq!(knapsack.solution().ks_value, 5);
+/// knapsack.add_item(Item { item_weight: 2, item_value: 3 });
+/// assert_eq!(knapsack.solution().ks_value, 7);
+/// knapsack.remove_item(0);
+/// assert_eq!(knapsack.solution().ks_value, 5);
+/// knapsack.set_capacity(6);
+/// assert_eq!(knapsack.solution().ks_value, 8);
+/// ```
+#[derive(Debug, Clone)]
+pub struct IncrementalKnapsack<W = u32, V = u32> {
+    problem: KnapsackProblem<W, V>,
+    width: usize,
+    /* One row per prefix of `kp_options`, each `width` long. */
+    table: Vec<V>,
+}
+
+impl<W, V> IncrementalKnapsack<W, V>
+    where W: KnapsackWeight, V: KnapsackValue {
+    /// Create a solver for `problem`.
+    ///
+    /// This takes the same time as `dynamic_knapsack()`.
+    ///
+    /// # Panics
+    /// If the value of the best knapsack doesn't fit in the value type.
+    pub fn new(problem: KnapsackProblem<W, V>) -> IncrementalKnapsack<W, V> {
+        let mut retval = IncrementalKnapsack {
+            problem: KnapsackProblem { kp_capacity: problem.kp_capacity, kp_options: Vec::new() },
+            width: 1,
+            table: vec![V::zero()],
+        };
+        for item in problem.kp_options {
+            retval.add_item(item);
+        }
+        retval
+    }
+
+    /// The problem as it currently stands.
+    pub fn problem(&self) -> &KnapsackProblem<W, V> {
+        &self.problem
+    }
+
+    /// Add `item` to the end of the options.
+    ///
+    /// This takes O(capacity) time, unless the table has to be made wider.
+    ///
+    /// # Panics
+    /// If the value of the best knapsack with `item` doesn't fit in the value type.
+    pub fn add_item(&mut self, item: Item<W, V>) {
+        self.problem.kp_options.push(item);
+        self.widen();
+        let rows  = self.problem.kp_options.len();
+        let width = self.width;
+        self.table.resize((rows + 1) * width, V::zero());
+        self.fill_rows(rows - 1, 0);
+    }
+
+    /// Remove and return the option at `index`.
+    ///
+    /// This takes O(capacity) time for each option after `index`.
+    ///
+    /// # Panics
+    /// If `index` is out of bounds.
+    pub fn remove_item(&mut self, index: usize) -> Item<W, V> {
+        let item  = self.problem.kp_options.remove(index);
+        let width = self.width;
+        self.table.drain((index + 1) * width..(index + 2) * width);
+        self.fill_rows(index, 0);
+        item
+    }
+
+    /// Change the capacity of the knapsack.
+    ///
+    /// This takes no time unless the table has to be made wider.
+    ///
+    /// # Panics
+    /// If the value of the best knapsack with the new capacity doesn't fit in the value type.
+    pub fn set_capacity(&mut self, capacity: W) {
+        self.problem.kp_capacity = capacity;
+        self.widen();
+    }
+
+    /// Returns an optimal solution to the current problem.
+    ///
+    /// This takes O(n) time.
+    pub fn solution(&self) -> KnapsackSolution<W, V> {
+        let column = table_width(&self.problem) - 1;
+        let keep   = reconstruct(&self.table, self.width, &self.problem.kp_options, column);
+        solution_from_flags(self.problem.clone(), &keep)
+    }
+
+    /*
+     * Recalculate every row for the items from `first` onwards, for the columns from `start`
+     * onwards.
+     */
+    fn fill_rows(&mut self, first: usize, start: usize) {
+        let width = self.width;
+        for (index, item) in self.problem.kp_options.iter().enumerate().skip(first) {
+            let (previous, current) = self.table[index * width..(index + 2) * width].split_at_mut(width);
+            if fill_columns(previous, &mut current[start..], start, column_weight(item), item.item_value).is_none() {
+                panic!("IncrementalKnapsack failed: {}", KnapsackError::ValueOverflow(index));
+            }
+        }
+    }
+
+    /* Make the table wide enough for the current problem, see the note at the top of this file. */
+    fn widen(&mut self) {
+        let needed = table_width(&self.problem);
+        if needed <= self.width {
+            return;
+        }
+        let capacity  = self.problem.kp_capacity.to_index().unwrap_or(usize::MAX);
+        let new_width = needed.max(self.width.saturating_mul(2)).min(capacity.saturating_add(1));
+        let old_width = self.width;
+        let rows      = self.problem.kp_options.len() + 1;
+        let mut table = vec![V::zero(); rows * new_width];
+        for (old_row, new_row) in self.table.chunks(old_width).zip(table.chunks_mut(new_width)) {
+            new_row[..old_width].copy_from_slice(old_row);
+        }
+        self.table = table;
+        self.width = new_width;
+        self.fill_rows(0, old_width);
+    }
+}
@@ -1,68 +1,160 @@
+use std::error::Error;
+use std::fmt;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+pub use self::numeric::{KnapsackValue, KnapsackWeight};
+pub use self::solver::{AutoSolver, BranchBoundSolver, CancellationToken, DynamicSolver, KnapsackSolver, LimitedSolution,
+                       RecursiveSolver, SolverLimits};
+pub use self::dynamic::{batch_knapsack, dynamic_knapsack, hirschberg_knapsack, try_dynamic_knapsack};
+pub use self::approximate::{approximate_knapsack, greedy_best_item_knapsack, greedy_knapsack, local_search_knapsack,
+                            ApproximateSolution};
+pub use self::branch_bound::{branch_and_bound_knapsack, BranchStatistics};
+pub use self::fractional::{fractional_knapsack, FractionalItem, FractionalSolution};
+pub use self::choice::{multiple_choice_knapsack, ChoiceProblem, ChoiceSolution};
+pub use self::constrained::{constrained_knapsack, KnapsackConstraints};
+pub use self::dimensional::{dimensional_knapsack, DimensionalItem, DimensionalProblem, DimensionalSolution};
+pub use self::parallel::parallel_knapsack;
+pub use self::incremental::IncrementalKnapsack;
+pub use self::multiple::{multiple_knapsack, MultipleProblem, MultipleSolution, MultipleStrategy};
+pub use self::ranked::{ranked_knapsacks, optimal_knapsacks, RankedKnapsacks};
+pub use self::format::{read_or_library, read_pisinger, read_problem, read_solution, write_problem, write_solution,
+                       OrLibraryInstance, ParseError, PisingerInstance};
+pub use self::subset::{count_change, count_subset_sums, make_change, subset_sum};
+pub use self::validate::{validate, SolutionViolation};
+pub use self::sensitivity::{sensitivity_analysis, ItemSensitivity, SensitivityReport};
+pub use self::quantity::{bounded_knapsack, try_bounded_knapsack, try_unbounded_knapsack, unbounded_knapsack, QuantityItem,
+                         QuantityProblem, QuantitySolution};
+
+mod numeric;
+mod dynamic;
+mod branch_bound;
+mod fractional;
+mod quantity;
+mod dimensional;
+mod multiple;
+mod ranked;
+mod constrained;
+mod choice;
+mod approximate;
+mod parallel;
+mod incremental;
+mod format;
+mod solver;
+mod subset;
+mod validate;
+mod sensitivity;
+
 /// The `Item` type -- represents one option to keep in the knapsack.
+///
+/// The weight and value can be any `KnapsackWeight` and `KnapsackValue` respectively, but default
+/// to `u32`.
+/// Only `best_knapsack()`, `try_best_knapsack()`, `dynamic_knapsack()`, `try_dynamic_knapsack()`,
+/// `hirschberg_knapsack()`, `batch_knapsack()`, `parallel_knapsack()` and `IncrementalKnapsack`
+/// accept other types, the other solvers work on the defaults.
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Item {
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Item<W = u32, V = u32> {
     /// weight of the item, which limits what can be stored in the knapsack.
-    pub item_weight: u32,
+    pub item_weight: W,
     /// value of the item, which decides our preference.
-    pub item_value: u32,
+    pub item_value: V,
 }
 
 /// The `KnapsackProblem` type -- represents the initial problem.
 #[derive(Debug, Clone)]
-pub struct KnapsackProblem {
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct KnapsackProblem<W = u32, V = u32> {
     /// capacity of the knapsack -- how much the sum of Items.weight can reach.
-    pub kp_capacity: u32,
+    pub kp_capacity: W,
     /// options -- the list of items that we have available for carrying.
-    pub kp_options: Vec<Item>,
+    pub kp_options: Vec<Item<W, V>>,
+}
+
+/// The `KnapsackError` type -- the reasons a knapsack problem can't be solved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KnapsackError {
+    /// The value of the best knapsack doesn't fit in the value type.
+    /// Holds the index into `kp_options` of the item whose addition overflowed.
+    ValueOverflow(usize),
+    /// A constraint refers to this index, which isn't in `kp_options`.
+    UnknownOption(usize),
+    /// The constraints never allow the item at this index of `kp_options` to be taken.
+    Contradiction(usize),
+}
+
+impl fmt::Display for KnapsackError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            KnapsackError::ValueOverflow(index) =>
+                write!(f, "value overflowed when adding item {} of the options", index),
+            KnapsackError::UnknownOption(index) =>
+                write!(f, "constraint refers to item {}, which is not one of the options", index),
+            KnapsackError::Contradiction(index) =>
+                write!(f, "constraints contradict each other, item {} can never be taken", index),
+        }
+    }
 }
 
+impl Error for KnapsackError {}
+
 /// The `KnapsackSolution` type -- represents a filling of the knapsack.
 #[derive(Debug)]
-pub struct KnapsackSolution {
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct KnapsackSolution<W = u32, V = u32> {
     /// weight of the knapsack -- sum of all Item.weight in items.
     /// this has no real purpose, it's just more conveniant to access a member than sum the
     /// items.weight values up.
-    pub ks_weight: u32,
+    pub ks_weight: W,
     /// The remaining capacity in the knapsack -- the size of the knapsack originally minus the
     /// sum of items.weights.
-    pub ks_capacity: u32,
+    pub ks_capacity: W,
     /// The value of this knapsack -- the sum of items.value.
     /// Again, this is just for conveniance, as the information is already stored in the items
     /// vector.
-    pub ks_value: u32,
+    pub ks_value: V,
     /// The list of items stored in the knapsack in this solution.
-    pub ks_items: Vec<Item>,
+    pub ks_items: Vec<Item<W, V>>,
 }
 
-fn vector_same_set<T: PartialEq>(left: &[T], right: &[T])
-    -> bool {
-
-    if left.len() != right.len() {
-        return false;
-    }
+/*
+ * For each element of `left`, find the index of an equal element of `right`, never using the same
+ * element of `right` twice.
+ * Elements of `left` with no unused match get `None`.
+ */
+fn matched_indices<T: PartialEq>(left: &[T], right: &[T])
+    -> Vec<Option<usize>> {
 
-    let mut marker = vec![0; left.len()];
+    let mut marker  = vec![0; right.len()];
+    let mut indices = Vec::with_capacity(left.len());
 
     for item in left {
-        let mut found_item = false;
+        let mut found_index = None;
         for (index, value) in right.iter().enumerate() {
             if *value == *item && marker[index] == 0 {
-                found_item    = true;
+                found_index   = Some(index);
                 marker[index] = 1;
                 break;
             }
         }
-
-        if !found_item {
-            return false;
-        }
+        indices.push(found_index);
     }
 
-    // Apparently not possible on the stable release channel.
-    // #[cfg(test)]
-    // assert!(marker.iter().all(| value: &u32 | { *value == 1 }));
+    indices
+}
 
-    true
+/*
+ * As `matched_indices()`, but returns `None` if there's an element of `left` with no unused
+ * match.
+ */
+fn matching_indices<T: PartialEq>(left: &[T], right: &[T])
+    -> Option<Vec<usize>> {
+    matched_indices(left, right).into_iter().collect()
+}
+
+fn vector_same_set<T: PartialEq>(left: &[T], right: &[T])
+    -> bool {
+    left.len() == right.len() && matching_indices(left, right).is_some()
 }
 
 /* Allow access to vector_same_set() from inside the test module. */
@@ -84,7 +176,7 @@ pub fn vector_same_set_test<T: PartialEq + Clone>(left: &Vec<T>, right: &Vec<T>)
     vector_same_set(left, right)
 }
 
-impl PartialEq for KnapsackSolution {
+impl<W: PartialEq, V: PartialEq> PartialEq for KnapsackSolution<W, V> {
     fn eq(&self, other: &Self) -> bool {
         let attributes_same =
             self.ks_weight   == other.ks_weight   &&
@@ -95,10 +187,72 @@ impl PartialEq for KnapsackSolution {
     }
 }
 
-impl Eq for KnapsackSolution {}
+impl<W: Eq, V: Eq> Eq for KnapsackSolution<W, V> {}
+
+impl<W: PartialEq, V: PartialEq> KnapsackSolution<W, V> {
+    /// Returns the index into `options` of each item in the knapsack, in the same order as
+    /// `ks_items`.
+    ///
+    /// This is how to trace a solution back to whatever the items represent, by keeping the
+    /// things the items were made from in a vector in the same order as `kp_options`.
+    /// Items with the same weight and value are interchangeable as far as any solver is concerned,
+    /// so where there are duplicates the earliest index not already used is returned.
+    ///
+    /// Returns `None` if the knapsack holds an item (or more copies of an item) that isn't in
+    /// `options`.
+    ///
+    /// ```
+    /// use rust_algorithms::knapsack_problem::{best_knapsack, KnapsackProblem, Item};
+    ///
+    /// let servers = vec![("alpha", 4, 3), ("beta", 2, 2), ("gamma", 2, 2)];
+    /// let problem = KnapsackProblem {
+    ///     kp_capacity: 4,
+    ///     kp_options: servers.iter()
+    ///                        .map(|&(_, weight, value)| Item { item_weight: weight, item_value: value })
+    ///                        .collect(),
+    /// };
+    /// let solution = best_knapsack(problem.clone());
+    /// let chosen: Vec<&str> = solution.option_indices(&problem.kp_options).unwrap()
+    ///                                 .into_iter()
+    ///                                 .map(|index| servers[index].0)
+    ///                                 .collect();
+    /// assert_eq!(chosen, vec!["beta", "gamma"]);
+    /// ```
+    pub fn option_indices(&self, options: &[Item<W, V>])
+        -> Option<Vec<usize>> {
+        matching_indices(&self.ks_items, options)
+    }
+}
+
+/*
+ * Build the solution that keeps each of `problem.kp_options` whose flag in `keep` is set.
+ * Most of the solvers work out which items to take by index, this saves each of them from
+ * totalling up the result.
+ */
+fn solution_from_flags<W, V>(problem: KnapsackProblem<W, V>, keep: &[bool])
+    -> KnapsackSolution<W, V>
+    where W: KnapsackWeight, V: KnapsackValue {
+    let ks_items: Vec<Item<W, V>> = problem.kp_options.into_iter()
+        .zip(keep)
+        .filter_map(|(item, &keep)| if keep { Some(item) } else { None })
+        .collect();
+    let ks_weight = ks_items.iter().fold(W::zero(), |acc, item| acc + item.item_weight);
+    KnapsackSolution {
+        ks_capacity : problem.kp_capacity - ks_weight,
+        ks_value    : ks_items.iter().fold(V::zero(), |acc, item| acc + item.item_value),
+        ks_weight,
+        ks_items,
+    }
+}
 
 /// Returns an optimal solution to the `KnapsackProblem`
 ///
+/// This takes O(2^n) time, use `RecursiveSolver` with `SolverLimits` to give up early.
+///
+/// # Panics
+/// If the value of the best knapsack doesn't fit in the value type, use `try_best_knapsack()` to handle
+/// that case.
+///
 /// ```
 /// use rust_algorithms::knapsack_problem::{best_knapsack, KnapsackProblem, KnapsackSolution, Item};
 ///
@@ -114,42 +268,75 @@ impl Eq for KnapsackSolution {}
 ///                 ks_items:    vec![Item { item_weight: 1,  item_value: 1 }],
 ///             });
 /// ```
-pub fn best_knapsack(mut problem: KnapsackProblem)
-    -> KnapsackSolution {
+pub fn best_knapsack<W, V>(problem: KnapsackProblem<W, V>)
+    -> KnapsackSolution<W, V>
+    where W: KnapsackWeight, V: KnapsackValue {
+    match try_best_knapsack(problem) {
+        Ok(solution) => solution,
+        Err(error) => panic!("best_knapsack() failed: {}", error),
+    }
+}
+
+/// Returns an optimal solution to the `KnapsackProblem`, or an error if its value would overflow.
+///
+/// The weight of a solution can never overflow, as it is bounded by `kp_capacity`.
+/// The value can, and this returns `KnapsackError::ValueOverflow` exactly when the best possible
+/// value doesn't fit in the value type (for floats, when it isn't finite).
+/// Choosing a wider type, e.g. `u64` or `u128`, avoids the overflow.
+/// The error holds the index into `kp_options` of the item that took the total over the limit.
+///
+/// ```
+/// use rust_algorithms::knapsack_problem::{try_best_knapsack, KnapsackProblem, KnapsackError, Item};
+///
+/// assert_eq!(try_best_knapsack( KnapsackProblem {
+///                                   kp_capacity: 3,
+///                                   kp_options: vec![Item { item_weight: 1, item_value: u32::MAX },
+///                                                    Item { item_weight: 1, item_value: 1 }],
+///                               } ),
+///             Err(KnapsackError::ValueOverflow(1)));
+/// ```
+pub fn try_best_knapsack<W, V>(mut problem: KnapsackProblem<W, V>)
+    -> Result<KnapsackSolution<W, V>, KnapsackError>
+    where W: KnapsackWeight, V: KnapsackValue {
     let test_item    = match problem.kp_options.pop() {
         Some(x) => x,
-        None => return KnapsackSolution {
-            ks_weight   : 0,
-            ks_value    : 0,
+        None => return Ok(KnapsackSolution {
+            ks_weight   : W::zero(),
+            ks_value    : V::zero(),
             ks_capacity : problem.kp_capacity,
             ks_items    : problem.kp_options
-        }
+        })
     };
 
+    let test_index   = problem.kp_options.len();
     let other_items  = problem.kp_options.clone();
     let cur_capacity = problem.kp_capacity;
 
-    let without_item = best_knapsack(problem);
+    let without_item = try_best_knapsack(problem)?;
 
     if cur_capacity < test_item.item_weight {
-        without_item
+        Ok(without_item)
     } else {
-        let mut with_item = best_knapsack(KnapsackProblem {
+        let mut with_item = try_best_knapsack(KnapsackProblem {
             kp_capacity: cur_capacity - test_item.item_weight,
             kp_options: other_items,
-        });
-        /* TODO -- Question: what should be done in case of integer overflow? */
-        if (with_item.ks_value  +  test_item.item_value) > without_item.ks_value {
-            with_item.ks_weight += test_item.item_weight;
-            with_item.ks_value  += test_item.item_value;
+        })?;
+        /*
+         * If this overflows then the value with the item is larger than anything without it, so
+         * the best value overflows too.
+         */
+        let with_value = with_item.ks_value.checked_add(test_item.item_value)
+            .ok_or(KnapsackError::ValueOverflow(test_index))?;
+        if with_value > without_item.ks_value {
+            with_item.ks_weight  = with_item.ks_weight + test_item.item_weight;
+            with_item.ks_value   = with_value;
             with_item.ks_items.push(test_item);
-            with_item
+            Ok(with_item)
         } else {
-            without_item
+            Ok(without_item)
         }
     }
 }
 
-
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,173 @@
+/*
+ * The multiple knapsack problem -- several knapsacks (bins), each with its own capacity, and each
+ * item can go in at most one of them.
+ *
+ * The exact solver is a branch and bound search over which bin (if any) each item goes in.
+ * Pretending all the bins are one big knapsack and allowing fractions of items gives an upper
+ * bound on the value reachable from any node.
+ * Bins with the same remaining capacity are interchangeable, so we only ever try the first of
+ * them, which removes a lot of symmetric branches when many bins are the same size.
+ *
+ * The greedy solver takes items in order of value per unit weight and puts each in the fullest
+ * bin that it still fits in.
+ */
+
+use super::{Item, KnapsackSolution};
+use super::branch_bound::{by_ratio, fractional_bound};
+
+/// The `MultipleProblem` type -- represents a problem with several knapsacks to fill.
+#[derive(Debug, Clone)]
+pub struct MultipleProblem {
+    /// capacity of each knapsack.
+    pub mp_capacities: Vec<u32>,
+    /// options -- the list of items that we have available to share between the knapsacks.
+    pub mp_options: Vec<Item>,
+}
+
+/// The `MultipleSolution` type -- represents an assignment of items to several knapsacks.
+#[derive(Debug, PartialEq, Eq)]
+pub struct MultipleSolution {
+    /// The filling of each knapsack, in the same order as the capacities in the problem.
+    pub ms_knapsacks: Vec<KnapsackSolution>,
+    /// The items that were not put in any knapsack.
+    pub ms_unassigned: Vec<Item>,
+    /// The total value of all knapsacks.
+    pub ms_value: u32,
+}
+
+/// The `MultipleStrategy` type -- how `multiple_knapsack()` should assign items.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MultipleStrategy {
+    /// Find an optimal assignment, which is exponential in the worst case.
+    Exact,
+    /// Assign items greedily, which takes O(n * log(n) + n * knapsacks) time but may miss the
+    /// optimum.
+    Greedy,
+}
+
+struct MultipleSearch<'a> {
+    items: &'a [Item],
+    remaining: Vec<u64>,
+    assignment: Vec<Option<usize>>,
+    best_value: u64,
+    best_assignment: Vec<Option<usize>>,
+}
+
+impl<'a> MultipleSearch<'a> {
+    fn search(&mut self, index: usize, value: u64) {
+        if value > self.best_value {
+            self.best_value = value;
+            self.best_assignment.copy_from_slice(&self.assignment);
+        }
+        if index == self.items.len() {
+            return;
+        }
+
+        let total_remaining = self.remaining.iter().sum();
+        if value + fractional_bound(&self.items[index..], total_remaining) <= self.best_value {
+            return;
+        }
+
+        let weight = self.items[index].item_weight as u64;
+        for bin in 0..self.remaining.len() {
+            let capacity = self.remaining[bin];
+            if weight > capacity || self.remaining[..bin].contains(&capacity) {
+                continue;
+            }
+            self.remaining[bin]     -= weight;
+            self.assignment[index]   = Some(bin);
+            self.search(index + 1, value + self.items[index].item_value as u64);
+            self.assignment[index]   = None;
+            self.remaining[bin]     += weight;
+        }
+        self.search(index + 1, value);
+    }
+}
+
+fn exact_assignment(items: &[Item], capacities: &[u32]) -> Vec<Option<usize>> {
+    let mut search = MultipleSearch {
+        items,
+        remaining: capacities.iter().map(|&capacity| capacity as u64).collect(),
+        assignment: vec![None; items.len()],
+        best_value: 0,
+        best_assignment: vec![None; items.len()],
+    };
+    search.search(0, 0);
+    search.best_assignment
+}
+
+fn greedy_assignment(items: &[Item], capacities: &[u32]) -> Vec<Option<usize>> {
+    let mut remaining = capacities.to_vec();
+    items.iter()
+        .map(|item| {
+            if item.item_value == 0 {
+                return None;
+            }
+            let bin = (0..remaining.len())
+                .filter(|&bin| remaining[bin] >= item.item_weight)
+                .min_by_key(|&bin| remaining[bin]);
+            if let Some(bin) = bin {
+                remaining[bin] -= item.item_weight;
+            }
+            bin
+        })
+        .collect()
+}
+
+/// Distributes the `problem.mp_options` between the knapsacks, maximising the total value.
+///
+/// ```
+/// use rust_algorithms::knapsack_problem::{multiple_knapsack, MultipleProblem, MultipleStrategy, Item};
+///
+/// let solution = multiple_knapsack(MultipleProblem {
+///     mp_capacities: vec![5, 3],
+///     mp_options: vec![Item { item_weight: 3, item_value: 3 },
+///                      Item { item_weight: 2, item_value: 2 },
+///                      Item { item_weight: 3, item_value: 3 },
+///                      Item { item_weight: 4, item_value: 1 }],
+/// }, MultipleStrategy::Exact);
+/// assert_eq!(solution.ms_value, 8);
+/// assert_eq!(solution.ms_knapsacks[0].ks_weight, 5);
+/// assert_eq!(solution.ms_knapsacks[1].ks_weight, 3);
+/// assert_eq!(solution.ms_unassigned, vec![Item { item_weight: 4, item_value: 1 }]);
+/// ```
+pub fn multiple_knapsack(problem: MultipleProblem, strategy: MultipleStrategy)
+    -> MultipleSolution {
+    let mut order: Vec<usize> = (0..problem.mp_options.len()).collect();
+    order.sort_by(|&left, &right| by_ratio(&problem.mp_options[left], &problem.mp_options[right]));
+    let sorted: Vec<Item> = order.iter().map(|&index| problem.mp_options[index].clone()).collect();
+
+    let sorted_assignment = match strategy {
+        MultipleStrategy::Exact  => exact_assignment(&sorted, &problem.mp_capacities),
+        MultipleStrategy::Greedy => greedy_assignment(&sorted, &problem.mp_capacities),
+    };
+    let mut assignment = vec![None; problem.mp_options.len()];
+    for (position, &index) in order.iter().enumerate() {
+        assignment[index] = sorted_assignment[position];
+    }
+
+    let mut ms_knapsacks: Vec<KnapsackSolution> = problem.mp_capacities.iter()
+        .map(|&capacity| KnapsackSolution {
+            ks_weight: 0, ks_capacity: capacity, ks_value: 0, ks_items: Vec::new(),
+        })
+        .collect();
+    let mut ms_unassigned = Vec::new();
+    for (item, bin) in problem.mp_options.into_iter().zip(assignment) {
+        match bin {
+            Some(bin) => {
+                let knapsack = &mut ms_knapsacks[bin];
+                knapsack.ks_weight   += item.item_weight;
+                knapsack.ks_capacity -= item.item_weight;
+                knapsack.ks_value    += item.item_value;
+                knapsack.ks_items.push(item);
+            },
+            None => ms_unassigned.push(item),
+        }
+    }
+
+    MultipleSolution {
+        ms_value: ms_knapsacks.iter().map(|knapsack| knapsack.ks_value).sum(),
+        ms_knapsacks,
+        ms_unassigned,
+    }
+}
@@ -0,0 +1,79 @@
+/*
+ * Traits describing the numbers that can be used as weights and values of `Item`s.
+ *
+ * Weights have to be whole numbers, as the dynamic programming solvers index tables by them, but
+ * values only need to be added and compared, so can be floats (e.g. amounts of money).
+ *
+ * The signed integers implement these traits too, mainly so that a problem written with integer
+ * literals and no type annotations (which Rust defaults to `i32`) can still be solved.
+ * Negative weights and values don't make much sense, and the solvers don't promise anything
+ * about them.
+ */
+
+use std::fmt::Debug;
+use std::ops::{Add, Sub};
+
+/// Numbers that can be used as the `item_value` of an `Item`.
+///
+/// ```
+/// use rust_algorithms::knapsack_problem::{dynamic_knapsack, KnapsackProblem, Item};
+///
+/// let solution = dynamic_knapsack(KnapsackProblem {
+///     kp_capacity: 10u64,
+///     kp_options: vec![Item { item_weight: 6, item_value: 2.50 },
+///                      Item { item_weight: 5, item_value: 1.25 },
+///                      Item { item_weight: 5, item_value: 1.50 }],
+/// });
+/// assert_eq!(solution.ks_value, 2.75);
+/// assert_eq!(solution.ks_capacity, 0);
+/// ```
+pub trait KnapsackValue: Copy + PartialOrd + Add<Output = Self> + Debug {
+    /// The value of an empty knapsack.
+    fn zero() -> Self;
+    /// Add two values, returning `None` if the result can't be represented.
+    fn checked_add(self, other: Self) -> Option<Self>;
+}
+
+/// Numbers that can be used as the `item_weight` of an `Item` and the capacity of a knapsack.
+pub trait KnapsackWeight: Copy + Ord + Add<Output = Self> + Sub<Output = Self> + Debug {
+    /// The weight of an empty knapsack.
+    fn zero() -> Self;
+    /// Convert to an index into a table, returning `None` if that isn't possible.
+    fn to_index(self) -> Option<usize>;
+}
+
+macro_rules! integer_numbers {
+    ($($number:ty),*) => {
+        $(
+            impl KnapsackValue for $number {
+                fn zero() -> Self { 0 }
+                fn checked_add(self, other: Self) -> Option<Self> { <$number>::checked_add(self, other) }
+            }
+
+            impl KnapsackWeight for $number {
+                fn zero() -> Self { 0 }
+                fn to_index(self) -> Option<usize> {
+                    use std::convert::TryFrom;
+                    usize::try_from(self).ok()
+                }
+            }
+        )*
+    }
+}
+
+macro_rules! float_numbers {
+    ($($number:ty),*) => {
+        $(
+            impl KnapsackValue for $number {
+                fn zero() -> Self { 0.0 }
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    let sum = self + other;
+                    if sum.is_finite() { Some(sum) } else { None }
+                }
+            }
+        )*
+    }
+}
+
+integer_numbers!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+float_numbers!(f32, f64);
@@ -0,0 +1,161 @@
+/*
+ * Dynamic programming over several threads.
+ *
+ * Each row of the table only depends on the row before it, so the cells of a row can be filled
+ * in any order.
+ * We start the threads once, give each of them a fixed run of columns, and have them fill their
+ * run of every row in turn, all waiting at a barrier before moving on to the next row.
+ * The table ends up exactly the same as the one `dynamic_knapsack()` fills, and the items are
+ * chosen from it in the same way, so the solutions are identical.
+ *
+ * Every thread reads the whole of the previous row while the others write their own runs of the
+ * current one, which the borrow checker can't see is fine, so the table is shared through a raw
+ * pointer (see `SharedTable`).
+ *
+ * Waiting at the barrier for every row isn't free, so each thread needs a decent number of
+ * columns to be worth it, otherwise it's quicker to fill the table on the current thread.
+ */
+
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::slice;
+use std::sync::Barrier;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use super::{KnapsackError, KnapsackProblem, KnapsackSolution, KnapsackValue, KnapsackWeight, solution_from_flags};
+use super::dynamic::{column_weight, fill_columns, reconstruct, table_width};
+
+/* Fewest columns worth handing to a thread of their own. */
+const MIN_COLUMNS_PER_THREAD: usize = 4096;
+
+/*
+ * The table, shared between the threads filling it.
+ *
+ * NOTE the `unsafe` code is justified by how the threads take turns:
+ * 1)   While filling row `r + 1` every thread only writes to its own run of columns of that row,
+ *      and the runs don't overlap, so no two threads write to the same cell.
+ * 2)   Row `r` is only read while row `r + 1` is being filled, and nobody writes to row `r` then.
+ * 3)   Every thread waits at the barrier between rows, which orders all the writes to row `r + 1`
+ *      before any of the reads of it.
+ * 4)   The threads are all joined before the table is used again, so nothing outlives the
+ *      borrow of the table.
+ */
+struct SharedTable<V> {
+    cells: *mut V,
+    width: usize,
+}
+
+unsafe impl<V: Send + Sync> Sync for SharedTable<V> {}
+
+/*
+ * Fill columns `start..end` of every row after the first, where `steps` holds the column weight
+ * and value of each item, waiting for the other threads at `barrier` after each row.
+ * If filling panics (a value overflowing) we still have to wait at the barrier, or the other
+ * threads would wait for us forever, so we tell them the first row that failed through
+ * `failed_row` and all stop after the barrier of that row, handing back what the panic was to be
+ * resumed once everyone has stopped.
+ *
+ * NOTE a thread that is the last to reach the barrier of row `r` carries straight on to the next
+ *      row, and can fail there before the others have even woken up from row `r`.
+ *      That's why we record which row failed rather than just that something did: the others
+ *      must still fill their run of the next row and meet it at that row's barrier.
+ */
+fn fill_run<V: KnapsackValue>(table: &SharedTable<V>, steps: &[(usize, V)], start: usize, end: usize,
+                               barrier: &Barrier, failed_row: &AtomicUsize)
+    -> Option<Box<dyn Any + Send>> {
+    for (index, &(weight, value)) in steps.iter().enumerate() {
+        let filled = panic::catch_unwind(AssertUnwindSafe(|| {
+            let (previous, current) = unsafe {
+                (slice::from_raw_parts(table.cells.add(index * table.width), table.width),
+                 slice::from_raw_parts_mut(table.cells.add((index + 1) * table.width + start), end - start))
+            };
+            if fill_columns(previous, current, start, weight, value).is_none() {
+                panic!("parallel_knapsack() failed: {}", KnapsackError::ValueOverflow(index));
+            }
+        }));
+        if let Err(payload) = filled {
+            failed_row.fetch_min(index, Ordering::Relaxed);
+            barrier.wait();
+            return Some(payload);
+        }
+        barrier.wait();
+        if failed_row.load(Ordering::Relaxed) <= index {
+            return None;
+        }
+    }
+    None
+}
+
+/// Returns an optimal solution to the `KnapsackProblem` using dynamic programming, filling each
+/// row of the table using up to `threads` threads.
+///
+/// If `threads` is zero then the number of threads the system suggests is used.
+/// The solution is always the same as `dynamic_knapsack()` (and hence `best_knapsack()`) would
+/// return, however many threads are used.
+///
+/// # Panics
+/// If the value of the best knapsack doesn't fit in the value type.
+///
+/// ```
+/// use rust_algorithms::knapsack_problem::{dynamic_knapsack, parallel_knapsack, KnapsackProblem, Item};
+///
+/// let problem = KnapsackProblem {
+///     kp_capacity: 5,
+///     kp_options: vec![Item { item_weight: 3, item_value: 4 },
+///                      Item { item_weight: 4, item_value: 5 },
+///                      Item { item_weight: 2, item_value: 3 }],
+/// };
+/// assert_eq!(parallel_knapsack(problem.clone(), 4), dynamic_knapsack(problem));
+/// ```
+pub fn parallel_knapsack<W, V>(problem: KnapsackProblem<W, V>, threads: usize)
+    -> KnapsackSolution<W, V>
+    where W: KnapsackWeight, V: KnapsackValue + Send + Sync {
+    let threads = if threads == 0 {
+        thread::available_parallelism().map(|count| count.get()).unwrap_or(1)
+    } else {
+        threads
+    };
+    let width = table_width(&problem);
+    let rows  = problem.kp_options.len() + 1;
+    let mut table = vec![V::zero(); rows * width];
+
+    let threads = threads.min(width / MIN_COLUMNS_PER_THREAD).max(1);
+    if threads == 1 {
+        for (index, item) in problem.kp_options.iter().enumerate() {
+            let (previous, current) = table[index * width..(index + 2) * width].split_at_mut(width);
+            if fill_columns(previous, current, 0, column_weight(item), item.item_value).is_none() {
+                panic!("parallel_knapsack() failed: {}", KnapsackError::ValueOverflow(index));
+            }
+        }
+    } else {
+        let shared  = SharedTable { cells: table.as_mut_ptr(), width };
+        let barrier = Barrier::new(threads);
+        let failed  = AtomicUsize::new(usize::MAX);
+        let steps: Vec<(usize, V)> = problem.kp_options.iter()
+            .map(|item| (column_weight(item), item.item_value))
+            .collect();
+        let steps   = &steps;
+        /* Thread `t` gets columns `bounds(t)..bounds(t + 1)`, the current thread is thread 0. */
+        let bounds  = |worker: usize| worker * width / threads;
+        let panicked = thread::scope(|scope| {
+            let (shared, barrier, failed) = (&shared, &barrier, &failed);
+            let handles: Vec<_> = (1..threads)
+                .map(|worker| scope.spawn(move || {
+                    fill_run(shared, steps, bounds(worker), bounds(worker + 1), barrier, failed)
+                }))
+                .collect();
+            let mut panicked = fill_run(shared, steps, bounds(0), bounds(1), barrier, failed);
+            for handle in handles {
+                let payload = handle.join().expect("Table filling panics are caught");
+                panicked = panicked.or(payload);
+            }
+            panicked
+        });
+        if let Some(payload) = panicked {
+            panic::resume_unwind(payload);
+        }
+    }
+
+    let keep = reconstruct(&table, width, &problem.kp_options, width - 1);
+    solution_from_flags(problem, &keep)
+}
@@ -0,0 +1,294 @@
+/*
+ * Knapsack problems where more than one copy of an item can be taken.
+ *
+ * Bounded problems (at most `k` copies of each item) are reduced to a 0/1 problem by splitting
+ * the copies of each item into bundles of 1, 2, 4, ... copies, plus whatever is left over.
+ * Any number of copies up to `k` can be made from a subset of those bundles, so solving the 0/1
+ * problem over the bundles solves the original, while only adding O(log(k)) items per item
+ * rather than `k` of them.
+ *
+ * Unbounded problems are solved directly, with a table indexed by capacity alone.
+ */
+
+use super::{Item, KnapsackError, KnapsackProblem};
+use super::dynamic::dynamic_choice;
+
+/// The `QuantityItem` type -- represents a number of copies of one `Item`.
+///
+/// In a `QuantityProblem` this is how many copies are available, in a `QuantitySolution` this
+/// is how many copies were chosen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuantityItem {
+    /// the item that there are copies of.
+    pub qi_item: Item,
+    /// the number of copies.
+    pub qi_copies: u32,
+}
+
+/// The `QuantityProblem` type -- represents a bounded knapsack problem.
+#[derive(Debug, Clone)]
+pub struct QuantityProblem {
+    /// capacity of the knapsack -- how much the sum of Items.weight can reach.
+    pub qp_capacity: u32,
+    /// options -- the items available, and how many copies of each there are.
+    pub qp_options: Vec<QuantityItem>,
+}
+
+/// The `QuantitySolution` type -- represents a filling of the knapsack with copies of items.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuantitySolution {
+    /// weight of the knapsack -- sum of Item.weight times the number of copies.
+    pub qs_weight: u32,
+    /// The remaining capacity in the knapsack.
+    pub qs_capacity: u32,
+    /// The value of this knapsack -- sum of Item.value times the number of copies.
+    pub qs_value: u32,
+    /// The items stored in the knapsack, each with a non-zero number of copies, in the same order
+    /// as they were given in the problem.
+    pub qs_items: Vec<QuantityItem>,
+}
+
+impl QuantitySolution {
+    fn from_counts(options: Vec<Item>, counts: &[u32], capacity: u32)
+        -> QuantitySolution {
+        let qs_items: Vec<QuantityItem> = options.into_iter()
+            .zip(counts)
+            .filter(|&(_, &count)| count != 0)
+            .map(|(item, &count)| QuantityItem { qi_item: item, qi_copies: count })
+            .collect();
+        let qs_weight = qs_items.iter().map(|copies| copies.qi_item.item_weight * copies.qi_copies).sum();
+        QuantitySolution {
+            qs_capacity : capacity - qs_weight,
+            qs_value    : qs_items.iter().map(|copies| copies.qi_item.item_value * copies.qi_copies).sum(),
+            qs_weight,
+            qs_items,
+        }
+    }
+}
+
+/// Returns an optimal solution to the bounded knapsack problem, where there are a limited number
+/// of copies of each item.
+///
+/// This splits the copies of each item into O(log(copies)) bundles and solves the resulting 0/1
+/// problem with dynamic programming, so takes O(capacity * sum(log(copies))) time.
+///
+/// # Panics
+/// If the value of the best knapsack doesn't fit in a `u32`, use `try_bounded_knapsack()` to
+/// handle that case.
+///
+/// ```
+/// use rust_algorithms::knapsack_problem::{bounded_knapsack, QuantityProblem, QuantitySolution, QuantityItem, Item};
+///
+/// assert_eq!(bounded_knapsack( QuantityProblem {
+///                                  qp_capacity: 10,
+///                                  qp_options: vec![QuantityItem { qi_item: Item { item_weight: 3, item_value: 5 },
+///                                                                  qi_copies: 2 },
+///                                                   QuantityItem { qi_item: Item { item_weight: 2, item_value: 3 },
+///                                                                  qi_copies: 5 }],
+///                              } ),
+///             QuantitySolution {
+///                 qs_weight:   10,
+///                 qs_capacity: 0,
+///                 qs_value:    16,
+///                 qs_items:    vec![QuantityItem { qi_item: Item { item_weight: 3, item_value: 5 },
+///                                                  qi_copies: 2 },
+///                                   QuantityItem { qi_item: Item { item_weight: 2, item_value: 3 },
+///                                                  qi_copies: 2 }],
+///             });
+/// ```
+pub fn bounded_knapsack(problem: QuantityProblem)
+    -> QuantitySolution {
+    match try_bounded_knapsack(problem) {
+        Ok(solution) => solution,
+        Err(error) => panic!("bounded_knapsack() failed: {}", error),
+    }
+}
+
+/// Returns an optimal solution to the bounded knapsack problem, or an error if its value would
+/// overflow.
+///
+/// This returns `KnapsackError::ValueOverflow` exactly when the best possible value doesn't fit in
+/// a `u32`, holding the index into `qp_options` of the item whose copies took the total over the
+/// limit.
+///
+/// ```
+/// use rust_algorithms::knapsack_problem::{try_bounded_knapsack, KnapsackError, QuantityProblem, QuantityItem, Item};
+///
+/// assert_eq!(try_bounded_knapsack( QuantityProblem {
+///                                      qp_capacity: 10,
+///                                      qp_options: vec![QuantityItem { qi_item: Item { item_weight: 5, item_value: 1 << 31 },
+///                                                                      qi_copies: 2 }],
+///                                  } ),
+///             Err(KnapsackError::ValueOverflow(0)));
+/// ```
+pub fn try_bounded_knapsack(problem: QuantityProblem)
+    -> Result<QuantitySolution, KnapsackError> {
+    /*
+     * A bundle always fits on its own, so if its value overflows then so does the best value.
+     * The bundles are solved with `u64` values, which can't overflow, and we check the total of
+     * the ones taken afterwards.
+     */
+    let mut bundles = Vec::new();
+    let mut origins = Vec::new();
+    for (index, copies) in problem.qp_options.iter().enumerate() {
+        let item = &copies.qi_item;
+        /* No point making bundles of more copies than could ever fit. */
+        let mut remaining =
+            match problem.qp_capacity.checked_div(item.item_weight) {
+                Some(fit) => copies.qi_copies.min(fit),
+                None      => copies.qi_copies,
+            };
+        let mut size = 1;
+        while remaining > 0 {
+            let bundle = size.min(remaining);
+            let value  = item.item_value.checked_mul(bundle).ok_or(KnapsackError::ValueOverflow(index))?;
+            bundles.push(Item {
+                item_weight : item.item_weight * bundle,
+                item_value  : value as u64,
+            });
+            origins.push((index, bundle, value));
+            remaining -= bundle;
+            size       = size.saturating_mul(2);
+        }
+    }
+
+    let keep = dynamic_choice(&KnapsackProblem { kp_capacity: problem.qp_capacity, kp_options: bundles })
+        .map_err(|error| match error {
+            KnapsackError::ValueOverflow(bundle) => KnapsackError::ValueOverflow(origins[bundle].0),
+            other => other,
+        })?;
+    let mut counts = vec![0; problem.qp_options.len()];
+    let mut value  = 0u32;
+    for (&(index, bundle, bundle_value), _) in origins.iter().zip(keep).filter(|&(_, keep)| keep) {
+        value = value.checked_add(bundle_value).ok_or(KnapsackError::ValueOverflow(index))?;
+        counts[index] += bundle;
+    }
+
+    let options = problem.qp_options.into_iter().map(|copies| copies.qi_item).collect();
+    Ok(QuantitySolution::from_counts(options, &counts, problem.qp_capacity))
+}
+
+/// Returns an optimal solution to the unbounded knapsack problem, where any number of copies of
+/// each of `problem.kp_options` can be taken.
+///
+/// This takes O(n * capacity) time and O(capacity) memory.
+/// Only weights that the items can add up to get a column, so items whose weights share a large
+/// common factor (or that don't fit at all) need much less.
+///
+/// # Panics
+/// If there is an item with no weight but some value, as then there is no limit on the value of
+/// the knapsack.
+/// If the value of the best knapsack doesn't fit in a `u32`, use `try_unbounded_knapsack()` to
+/// handle that case.
+///
+/// ```
+/// use rust_algorithms::knapsack_problem::{unbounded_knapsack, KnapsackProblem, QuantitySolution, QuantityItem, Item};
+///
+/// assert_eq!(unbounded_knapsack( KnapsackProblem {
+///                                    kp_capacity: 7,
+///                                    kp_options: vec![Item { item_weight: 5, item_value: 6 },
+///                                                     Item { item_weight: 2, item_value: 2 }],
+///                                } ),
+///             QuantitySolution {
+///                 qs_weight:   7,
+///                 qs_capacity: 0,
+///                 qs_value:    8,
+///                 qs_items:    vec![QuantityItem { qi_item: Item { item_weight: 5, item_value: 6 },
+///                                                  qi_copies: 1 },
+///                                   QuantityItem { qi_item: Item { item_weight: 2, item_value: 2 },
+///                                                  qi_copies: 1 }],
+///             });
+/// ```
+pub fn unbounded_knapsack(problem: KnapsackProblem)
+    -> QuantitySolution {
+    match try_unbounded_knapsack(problem) {
+        Ok(solution) => solution,
+        Err(error) => panic!("unbounded_knapsack() failed: {}", error),
+    }
+}
+
+/* Greatest common divisor, with `gcd(0, b) == b`. */
+pub(super) fn gcd(mut a: u32, mut b: u32) -> u32 {
+    while b != 0 {
+        let remainder = a % b;
+        a = b;
+        b = remainder;
+    }
+    a
+}
+
+/// Returns an optimal solution to the unbounded knapsack problem, or an error if its value would
+/// overflow.
+///
+/// This returns `KnapsackError::ValueOverflow` exactly when the best possible value doesn't fit in
+/// a `u32`, holding the index into `kp_options` of the item whose copy took the total over the
+/// limit.
+///
+/// # Panics
+/// If there is an item with no weight but some value, as for `unbounded_knapsack()`.
+///
+/// ```
+/// use rust_algorithms::knapsack_problem::{try_unbounded_knapsack, KnapsackProblem, KnapsackError, Item};
+///
+/// assert_eq!(try_unbounded_knapsack( KnapsackProblem {
+///                                        kp_capacity: 4_000_000_000,
+///                                        kp_options: vec![Item { item_weight: 1_000_000_000, item_value: 1 << 31 }],
+///                                    } ),
+///             Err(KnapsackError::ValueOverflow(0)));
+/// ```
+pub fn try_unbounded_knapsack(problem: KnapsackProblem)
+    -> Result<QuantitySolution, KnapsackError> {
+    if problem.kp_options.iter().any(|item| item.item_weight == 0 && item.item_value != 0) {
+        panic!("unbounded_knapsack() given a weightless item with value, \
+                the best knapsack is infinitely valuable");
+    }
+
+    /*
+     * Every weight the items that fit can add up to is a multiple of the greatest common divisor
+     * of their weights, so the table only needs a column for each multiple up to the capacity.
+     * Column `c` is a weight of `c * step`.
+     */
+    let step = problem.kp_options.iter()
+        .map(|item| item.item_weight)
+        .filter(|&weight| weight != 0 && weight <= problem.kp_capacity)
+        .fold(0, gcd);
+    let width = match problem.kp_capacity.checked_div(step) {
+        Some(columns) => columns as usize + 1,
+        None          => 1,
+    };
+
+    /*
+     * `best[c]` is the best value in column `c`, and `last[c]` is the index of the last item
+     * added to reach it (if any), so that we can walk back down through the columns to find
+     * which items were taken.
+     * Anything added to `best` fits in the knapsack, so if it overflows so does the best value.
+     */
+    let mut best = vec![0u32; width];
+    let mut last = vec![None; width];
+    for column in 1..width {
+        for (index, item) in problem.kp_options.iter().enumerate() {
+            if item.item_weight == 0 || item.item_weight > problem.kp_capacity {
+                continue;
+            }
+            let columns = (item.item_weight / step) as usize;
+            if columns > column {
+                continue;
+            }
+            let value = best[column - columns].checked_add(item.item_value)
+                .ok_or(KnapsackError::ValueOverflow(index))?;
+            if value > best[column] {
+                best[column] = value;
+                last[column] = Some(index);
+            }
+        }
+    }
+
+    let mut counts    = vec![0; problem.kp_options.len()];
+    let mut remaining = width - 1;
+    while let Some(index) = last[remaining] {
+        counts[index] += 1;
+        remaining     -= (problem.kp_options[index].item_weight / step) as usize;
+    }
+
+    Ok(QuantitySolution::from_counts(problem.kp_options, &counts, problem.kp_capacity))
+}
@@ -0,0 +1,226 @@
+/*
+ * Enumerating knapsack solutions in order of decreasing value.
+ *
+ * Identical items are grouped together first, so that we never produce two solutions that only
+ * differ in which of some identical items were taken (they would compare equal anyway).
+ *
+ * We fill a dynamic programming table over the groups, where `table[g][c]` is the best value
+ * achievable with the first `g` groups and capacity `c`.
+ * Then we walk the tree of decisions (how many copies to take from the last group, then the one
+ * before that, ...) best-first.
+ * Each node is keyed by the value taken so far plus the best value possible with the undecided
+ * groups, which the table tells us exactly.
+ * No node can lead to anything better than its key, so when a complete solution comes off the
+ * heap nothing left on the heap can beat it, and the solutions come out in order.
+ *
+ * Because the keys are exact, following the best child of a node always leads straight to a
+ * solution of the same value, so each solution only costs O(n) heap operations to find.
+ *
+ * A filling whose value doesn't fit in a `u32` can't be returned, so we skip any branch whose
+ * value overflows.
+ * The table and the keys saturate instead, a saturated key is still at least the value of every
+ * filling below it that we can return, so the order is still right, we just might have to throw
+ * away some overflowing branches on the way to the next solution.
+ */
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::rc::Rc;
+use super::{Item, KnapsackProblem, KnapsackSolution};
+
+/* A persistent list of the choices made on the way to a node, shared between siblings. */
+struct Choice {
+    group: usize,
+    copies: u32,
+    previous: Option<Rc<Choice>>,
+}
+
+struct Node {
+    key: u32,
+    /* Order of insertion, so that nodes with the same key come out first-in first-out. */
+    sequence: u64,
+    groups_left: usize,
+    capacity: usize,
+    value: u32,
+    choices: Option<Rc<Choice>>,
+}
+
+impl PartialEq for Node {
+    fn eq(&self, other: &Node) -> bool { self.cmp(other) == Ordering::Equal }
+}
+
+impl Eq for Node {}
+
+impl PartialOrd for Node {
+    fn partial_cmp(&self, other: &Node) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+
+impl Ord for Node {
+    fn cmp(&self, other: &Node) -> Ordering {
+        self.key.cmp(&other.key).then(other.sequence.cmp(&self.sequence))
+    }
+}
+
+/// The `RankedKnapsacks` type -- an iterator over every filling of a knapsack, in order of
+/// decreasing value.
+///
+/// Created by `ranked_knapsacks()`.
+pub struct RankedKnapsacks {
+    capacity: u32,
+    /* Distinct items, and how many copies of each there are in the problem. */
+    groups: Vec<(Item, u32)>,
+    width: usize,
+    table: Vec<u32>,
+    heap: BinaryHeap<Node>,
+    sequence: u64,
+}
+
+impl RankedKnapsacks {
+    fn new(problem: KnapsackProblem) -> RankedKnapsacks {
+        let mut groups: Vec<(Item, u32)> = Vec::new();
+        for item in problem.kp_options {
+            match groups.iter().position(|group| group.0 == item) {
+                Some(position) => groups[position].1 += 1,
+                None => groups.push((item, 1)),
+            }
+        }
+
+        let total_weight = groups.iter()
+            .fold(0u32, |acc, group| acc.saturating_add(group.0.item_weight.saturating_mul(group.1)));
+        let width = problem.kp_capacity.min(total_weight) as usize + 1;
+        let mut table = vec![0u32; (groups.len() + 1) * width];
+        for (index, &(ref item, copies)) in groups.iter().enumerate() {
+            let (previous, current) = table[index * width..(index + 2) * width].split_at_mut(width);
+            for (capacity, cell) in current.iter_mut().enumerate() {
+                *cell = (0..copies + 1)
+                    .take_while(|&count| (item.item_weight as usize) * (count as usize) <= capacity)
+                    .map(|count| previous[capacity - (item.item_weight * count) as usize]
+                                     .saturating_add(item.item_value.saturating_mul(count)))
+                    .max()
+                    .unwrap_or(0);
+            }
+        }
+
+        let mut retval = RankedKnapsacks {
+            capacity: problem.kp_capacity,
+            groups,
+            width,
+            table,
+            heap: BinaryHeap::new(),
+            sequence: 0,
+        };
+        let (groups_left, capacity) = (retval.groups.len(), width - 1);
+        retval.push(groups_left, capacity, 0, None);
+        retval
+    }
+
+    fn push(&mut self, groups_left: usize, capacity: usize, value: u32, choices: Option<Rc<Choice>>) {
+        let key = value.saturating_add(self.table[groups_left * self.width + capacity]);
+        self.heap.push(Node { key, sequence: self.sequence, groups_left, capacity, value, choices });
+        self.sequence += 1;
+    }
+
+    fn solution(&self, mut choices: Option<Rc<Choice>>) -> KnapsackSolution {
+        let mut ks_items = Vec::new();
+        while let Some(choice) = choices {
+            for _ in 0..choice.copies {
+                ks_items.push(self.groups[choice.group].0.clone());
+            }
+            choices = choice.previous.clone();
+        }
+        ks_items.reverse();
+        let ks_weight = ks_items.iter().map(|item| item.item_weight).sum();
+        KnapsackSolution {
+            ks_capacity : self.capacity - ks_weight,
+            ks_value    : ks_items.iter().map(|item| item.item_value).sum(),
+            ks_weight,
+            ks_items,
+        }
+    }
+}
+
+impl Iterator for RankedKnapsacks {
+    type Item = KnapsackSolution;
+
+    fn next(&mut self) -> Option<KnapsackSolution> {
+        while let Some(node) = self.heap.pop() {
+            if node.groups_left == 0 {
+                return Some(self.solution(node.choices));
+            }
+
+            let group = node.groups_left - 1;
+            let (weight, value, copies) = {
+                let (ref item, copies) = self.groups[group];
+                (item.item_weight as usize, item.item_value, copies)
+            };
+            for count in 0..copies + 1 {
+                if weight * count as usize > node.capacity {
+                    break;
+                }
+                /* Taking more copies only adds more value, so once this overflows it always will. */
+                let total = match value.checked_mul(count).and_then(|added| node.value.checked_add(added)) {
+                    Some(total) => total,
+                    None => break,
+                };
+                let choices =
+                    if count == 0 {
+                        node.choices.clone()
+                    } else {
+                        Some(Rc::new(Choice { group, copies: count, previous: node.choices.clone() }))
+                    };
+                self.push(group, node.capacity - weight * count as usize, total, choices);
+            }
+        }
+        None
+    }
+}
+
+/// Returns an iterator over every different filling of the knapsack, best first.
+///
+/// Fillings that only differ in which of several identical items were taken are only returned
+/// once, and fillings whose value doesn't fit in a `u32` are skipped.
+/// The iterator is lazy, so taking the first `k` solutions only does the work needed to find
+/// those, but building it fills a dynamic programming table the same size as
+/// `dynamic_knapsack()` does.
+///
+/// ```
+/// use rust_algorithms::knapsack_problem::{ranked_knapsacks, KnapsackProblem, Item};
+///
+/// let values: Vec<u32> = ranked_knapsacks(KnapsackProblem {
+///                            kp_capacity: 5,
+///                            kp_options: vec![Item { item_weight: 3, item_value: 4 },
+///                                             Item { item_weight: 4, item_value: 5 },
+///                                             Item { item_weight: 2, item_value: 3 }],
+///                        })
+///                        .map(|solution| solution.ks_value)
+///                        .collect();
+/// assert_eq!(values, vec![7, 5, 4, 3, 0]);
+/// ```
+pub fn ranked_knapsacks(problem: KnapsackProblem)
+    -> RankedKnapsacks {
+    RankedKnapsacks::new(problem)
+}
+
+/// Returns an iterator over every optimal filling of the knapsack.
+///
+/// This is the start of `ranked_knapsacks()`, up until the value drops.
+///
+/// ```
+/// use rust_algorithms::knapsack_problem::{optimal_knapsacks, KnapsackProblem, Item};
+///
+/// let solutions: Vec<_> = optimal_knapsacks(KnapsackProblem {
+///                             kp_capacity: 4,
+///                             kp_options: vec![Item { item_weight: 4, item_value: 4 },
+///                                              Item { item_weight: 2, item_value: 2 },
+///                                              Item { item_weight: 2, item_value: 2 },
+///                                              Item { item_weight: 1, item_value: 1 }],
+///                         }).collect();
+/// assert_eq!(solutions.len(), 2);
+/// assert!(solutions.iter().all(|solution| solution.ks_value == 4));
+/// ```
+pub fn optimal_knapsacks(problem: KnapsackProblem)
+    -> impl Iterator<Item = KnapsackSolution> {
+    let mut ranked = ranked_knapsacks(problem).peekable();
+    let best_value = ranked.peek().map(|solution| solution.ks_value);
+    ranked.take_while(move |solution| Some(solution.ks_value) == best_value)
+}
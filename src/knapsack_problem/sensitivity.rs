@@ -0,0 +1,145 @@
+/*
+ * How far the numbers in a knapsack problem can move before an optimal solution stops being
+ * optimal.
+ *
+ * For each item we need the best value of any filling that takes it, and of any filling that
+ * leaves it out:
+ * - an item in the solution can lose value until the best filling without it catches up, and can
+ *   gain any amount,
+ * - an item left out can gain value until the best filling with it catches up, and can lose any
+ *   amount.
+ *
+ * Both of those come from two dynamic programming tables: one over the items before the item, and
+ * one over the items after it (filled from the end).
+ * Splitting the capacity between the two in every possible way gives the best filling of
+ * everything but that item, in O(capacity) for each item.
+ *
+ * The last row of the first table holds the best value for every smaller capacity, so the
+ * capacity can shrink down to the first column of that row that still reaches the optimum.
+ * That's the weight of the lightest optimal filling, which needn't be the solution we were given.
+ */
+
+use super::{KnapsackProblem, KnapsackSolution, validate};
+use super::dynamic::{column_weight, fill_columns, table_width};
+
+/// The `ItemSensitivity` type -- how much the value of one item can change while a solution stays
+/// optimal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ItemSensitivity {
+    /// whether the item is in the solution.
+    pub is_taken: bool,
+    /// how much the value can go down, `None` if there's no limit.
+    pub is_decrease: Option<u32>,
+    /// how much the value can go up, `None` if there's no limit.
+    pub is_increase: Option<u32>,
+}
+
+/// The `SensitivityReport` type -- how robust an optimal solution to a knapsack problem is.
+///
+/// Each tolerance is how far that one number can change, with everything else left alone, while
+/// the solution is still optimal (it may tie with other solutions at the limit).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SensitivityReport {
+    /// For each item of `kp_options`, how much its value can change.
+    pub sr_items: Vec<ItemSensitivity>,
+    /// How much `kp_capacity` can shrink before the optimal value goes down.
+    /// If another optimal solution is lighter, the given one may not fit by then.
+    pub sr_capacity_shrink: u32,
+}
+
+/* Best value of any filling of `capacity` that's split between `before` and `after`. */
+fn combine(before: &[u64], after: &[u64], capacity: usize) -> u64 {
+    (0..capacity + 1)
+        .map(|split| before[split] + after[capacity - split])
+        .max()
+        .unwrap_or(0)
+}
+
+/// Returns how much each number in `problem` can change while `solution` is still optimal.
+///
+/// Returns `None` if `solution` isn't a valid, optimal solution of `problem` (see `validate()` to
+/// find out what's wrong with an invalid one).
+/// When there are identical items, which of them the solution took is decided as by
+/// `option_indices()`.
+/// This takes O(n * capacity) time and memory, like `dynamic_knapsack()`.
+///
+/// ```
+/// use rust_algorithms::knapsack_problem::{sensitivity_analysis, dynamic_knapsack, ItemSensitivity, KnapsackProblem, Item};
+///
+/// let problem = KnapsackProblem {
+///     kp_capacity: 6,
+///     kp_options: vec![Item { item_weight: 3, item_value: 4 },
+///                      Item { item_weight: 4, item_value: 5 },
+///                      Item { item_weight: 2, item_value: 3 }],
+/// };
+/// let solution = dynamic_knapsack(problem.clone());
+/// let report = sensitivity_analysis(&problem, &solution).unwrap();
+/// // The solution is the last two items, worth 8, and the first item with the last is worth 7.
+/// assert_eq!(report.sr_items[0], ItemSensitivity { is_taken: false, is_decrease: None, is_increase: Some(1) });
+/// assert_eq!(report.sr_items[1], ItemSensitivity { is_taken: true, is_decrease: Some(1), is_increase: None });
+/// assert_eq!(report.sr_capacity_shrink, 0);
+/// ```
+pub fn sensitivity_analysis(problem: &KnapsackProblem, solution: &KnapsackSolution)
+    -> Option<SensitivityReport> {
+    if !validate(problem, solution).is_empty() {
+        return None;
+    }
+    let indices = solution.option_indices(&problem.kp_options)?;
+    let items   = &problem.kp_options;
+    let width   = table_width(problem);
+    let rows    = items.len() + 1;
+
+    /*
+     * Row `r` of `before` is the best value using the first `r` items, row `r` of `after` the best
+     * value using the items from `r` onwards.
+     */
+    let mut before = vec![0u64; rows * width];
+    let mut after  = vec![0u64; rows * width];
+    for (index, item) in items.iter().enumerate() {
+        let (previous, current) = before[index * width..(index + 2) * width].split_at_mut(width);
+        fill_columns(previous, current, 0, column_weight(item), item.item_value as u64)
+            .expect("u32 values can't add up past u64");
+    }
+    for (index, item) in items.iter().enumerate().rev() {
+        let (current, previous) = after[index * width..(index + 2) * width].split_at_mut(width);
+        fill_columns(previous, current, 0, column_weight(item), item.item_value as u64)
+            .expect("u32 values can't add up past u64");
+    }
+
+    let optimum = before[rows * width - 1];
+    if solution.ks_value as u64 != optimum {
+        return None;
+    }
+
+    let mut taken = vec![false; items.len()];
+    for index in indices {
+        taken[index] = true;
+    }
+    let sr_items = items.iter()
+        .enumerate()
+        .map(|(index, item)| {
+            let before  = &before[index * width..(index + 1) * width];
+            let after   = &after[(index + 1) * width..(index + 2) * width];
+            let weight  = column_weight(item);
+            if taken[index] {
+                let without = combine(before, after, width - 1);
+                ItemSensitivity { is_taken: true, is_decrease: Some((optimum - without) as u32), is_increase: None }
+            } else {
+                let with = if weight < width {
+                    Some(combine(before, after, width - 1 - weight) + item.item_value as u64)
+                } else {
+                    None
+                };
+                ItemSensitivity {
+                    is_taken: false,
+                    is_decrease: None,
+                    is_increase: with.map(|with| (optimum - with) as u32),
+                }
+            }
+        })
+        .collect();
+
+    let last_row = &before[(rows - 1) * width..];
+    let lightest = last_row.iter().position(|&value| value == optimum).unwrap_or(width - 1);
+    Some(SensitivityReport { sr_items, sr_capacity_shrink: problem.kp_capacity - lightest as u32 })
+}
@@ -0,0 +1,333 @@
+/*
+ * A common interface to the different ways of solving the knapsack problem, so that callers can
+ * pick an algorithm at run time (or let `AutoSolver` pick one) rather than calling a particular
+ * function.
+ *
+ * Solvers that search a tree can be given limits on how much work to do, in which case they
+ * return the best solution found before stopping, and say whether it's known to be optimal.
+ * Looking at the clock is much slower than visiting a node, so the deadline and the cancellation
+ * token are only checked every so often.
+ */
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+use super::{Item, KnapsackError, KnapsackProblem, KnapsackSolution, dynamic_knapsack, solution_from_flags};
+use super::branch_bound::limited_branch_and_bound;
+use super::dynamic::table_width;
+
+/* Problems with at most this many items are small enough to just try everything. */
+const RECURSIVE_ITEMS: usize = 12;
+/* The most cells we're happy for `AutoSolver` to put in a dynamic programming table. */
+const DYNAMIC_CELLS: usize = 1 << 24;
+/*
+ * How many nodes a search visits between looks at the deadline and the cancellation token, a
+ * power of two so that checking for a multiple of it is a mask.
+ */
+const CHECK_INTERVAL: u64 = 1024;
+
+/// The `CancellationToken` type -- lets another thread stop a solver that was given a clone of
+/// this token in its `SolverLimits`.
+///
+/// ```
+/// use std::thread;
+/// use rust_algorithms::knapsack_problem::{CancellationToken, KnapsackSolver, RecursiveSolver,
+///                                         SolverLimits, KnapsackProblem, Item};
+///
+/// let token = CancellationToken::new();
+/// let limits = SolverLimits { sl_cancel: Some(token.clone()), ..SolverLimits::default() };
+/// let problem = KnapsackProblem { kp_capacity: 1000, kp_options: vec![Item { item_weight: 3, item_value: 4 }; 100] };
+/// let search = thread::spawn(move || RecursiveSolver.solve_with_limits(problem, &limits));
+/// token.cancel();
+/// assert!(!search.join().unwrap().ls_optimal);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// A new token, which hasn't been cancelled.
+    pub fn new() -> CancellationToken {
+        CancellationToken::default()
+    }
+
+    /// Ask every solver using this token (or a clone of it) to stop.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether `cancel()` has been called on this token or a clone of it.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/* Tokens are equal when they're clones of each other, i.e. cancelling one cancels the other. */
+impl PartialEq for CancellationToken {
+    fn eq(&self, other: &CancellationToken) -> bool { Arc::ptr_eq(&self.0, &other.0) }
+}
+
+impl Eq for CancellationToken {}
+
+/// The `SolverLimits` type -- how much work a solver may do before returning the best solution
+/// it has found so far.
+///
+/// The default has no limits.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SolverLimits {
+    /// Stop after visiting this many nodes of the search tree.
+    pub sl_max_nodes: Option<u64>,
+    /// Stop once this time has passed.
+    pub sl_deadline: Option<Instant>,
+    /// Stop once this token has been cancelled.
+    pub sl_cancel: Option<CancellationToken>,
+}
+
+impl SolverLimits {
+    /* Whether a search that has visited `nodes` nodes has to stop. */
+    pub(super) fn exhausted(&self, nodes: u64) -> bool {
+        if self.sl_max_nodes.is_some_and(|max_nodes| nodes >= max_nodes) {
+            return true;
+        }
+        if nodes & (CHECK_INTERVAL - 1) != 0 {
+            return false;
+        }
+        self.sl_deadline.is_some_and(|deadline| Instant::now() >= deadline) ||
+            self.sl_cancel.as_ref().is_some_and(|token| token.is_cancelled())
+    }
+}
+
+/// The `LimitedSolution` type -- the best solution a solver found within its limits.
+#[derive(Debug, PartialEq, Eq)]
+pub struct LimitedSolution {
+    /// The best filling of the knapsack found.
+    pub ls_solution: KnapsackSolution,
+    /// Whether `ls_solution` is known to be optimal, i.e. the solver wasn't stopped by the limits
+    /// before it could prove that.
+    pub ls_optimal: bool,
+}
+
+/// A way of solving a `KnapsackProblem`.
+///
+/// ```
+/// use rust_algorithms::knapsack_problem::{AutoSolver, BranchBoundSolver, DynamicSolver, RecursiveSolver,
+///                                         KnapsackSolver, KnapsackProblem, Item};
+///
+/// let problem = KnapsackProblem {
+///     kp_capacity: 5,
+///     kp_options: vec![Item { item_weight: 3, item_value: 4 },
+///                      Item { item_weight: 4, item_value: 5 },
+///                      Item { item_weight: 2, item_value: 3 }],
+/// };
+/// let solvers: Vec<Box<dyn KnapsackSolver>> = vec![Box::new(RecursiveSolver), Box::new(DynamicSolver),
+///                                                  Box::new(BranchBoundSolver), Box::new(AutoSolver)];
+/// for solver in solvers {
+///     assert_eq!(solver.solve(problem.clone()).ks_value, 7, "{} failed", solver.name());
+/// }
+/// ```
+pub trait KnapsackSolver {
+    /// A short name for the algorithm, e.g. for logging which one was used.
+    fn name(&self) -> &'static str;
+
+    /// Returns the best solution found for `problem` without doing more work than `limits`
+    /// allow.
+    fn solve_with_limits(&self, problem: KnapsackProblem, limits: &SolverLimits) -> LimitedSolution;
+
+    /// Returns an optimal solution to `problem`.
+    fn solve(&self, problem: KnapsackProblem) -> KnapsackSolution {
+        self.solve_with_limits(problem, &SolverLimits::default()).ls_solution
+    }
+}
+
+/// The `RecursiveSolver` type -- solves problems the same way as `best_knapsack()`, trying every
+/// combination of items.
+///
+/// Each call of the recursion counts as a node for `SolverLimits::sl_max_nodes`.
+/// This is the way to run `best_knapsack()` with a deadline or a way to cancel it.
+/// Without limits the solution is the same as `best_knapsack()` returns, and like that function
+/// this panics if the best value doesn't fit in a `u32`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RecursiveSolver;
+
+struct RecursiveSearch<'a> {
+    items: &'a [Item],
+    limits: &'a SolverLimits,
+    nodes: u64,
+    stopped: bool,
+    /* The items taken by the calls further up, and their total value. */
+    path: Vec<bool>,
+    path_value: u64,
+    /* The best complete filling seen so far, for when the search is stopped. */
+    best_value: u64,
+    best_keep: Vec<bool>,
+}
+
+impl<'a> RecursiveSearch<'a> {
+    fn new(items: &'a [Item], limits: &'a SolverLimits) -> RecursiveSearch<'a> {
+        RecursiveSearch {
+            items,
+            limits,
+            nodes: 0,
+            stopped: false,
+            path: vec![false; items.len()],
+            path_value: 0,
+            best_value: 0,
+            best_keep: vec![false; items.len()],
+        }
+    }
+
+    /*
+     * The best value and the indices of the items taken, using the first `count` items.
+     * Together with the items on the path, whatever this returns is a complete filling of the
+     * knapsack, so we keep the best of those in case the search is stopped.
+     */
+    fn search(&mut self, count: usize, capacity: u32) -> (u32, Vec<usize>) {
+        let found = self.search_items(count, capacity);
+        if self.path_value + found.0 as u64 > self.best_value {
+            self.best_value = self.path_value + found.0 as u64;
+            self.best_keep.copy_from_slice(&self.path);
+            for &index in &found.1 {
+                self.best_keep[index] = true;
+            }
+        }
+        found
+    }
+
+    /*
+     * This makes the same decisions as `try_best_knapsack()`, so that the solutions are the
+     * same.
+     * Once stopped, every call returns the empty knapsack, so the result is always a valid
+     * filling.
+     */
+    fn search_items(&mut self, count: usize, capacity: u32) -> (u32, Vec<usize>) {
+        if count == 0 {
+            return (0, Vec::new());
+        }
+        if self.stopped || self.limits.exhausted(self.nodes) {
+            self.stopped = true;
+            return (0, Vec::new());
+        }
+        self.nodes += 1;
+
+        let index   = count - 1;
+        let item    = &self.items[index];
+        let without = self.search(index, capacity);
+        if capacity < item.item_weight {
+            return without;
+        }
+        self.path[index]  = true;
+        self.path_value  += item.item_value as u64;
+        let (value, mut taken) = self.search(index, capacity - item.item_weight);
+        self.path[index]  = false;
+        self.path_value  -= item.item_value as u64;
+        let with_value = match value.checked_add(item.item_value) {
+            Some(with_value) => with_value,
+            None => panic!("RecursiveSolver failed: {}", KnapsackError::ValueOverflow(index)),
+        };
+        if with_value > without.0 {
+            taken.push(index);
+            (with_value, taken)
+        } else {
+            without
+        }
+    }
+}
+
+impl KnapsackSolver for RecursiveSolver {
+    fn name(&self) -> &'static str { "recursive" }
+
+    fn solve_with_limits(&self, problem: KnapsackProblem, limits: &SolverLimits) -> LimitedSolution {
+        let mut search = RecursiveSearch::new(&problem.kp_options, limits);
+        let (_, taken) = search.search(problem.kp_options.len(), problem.kp_capacity);
+        /*
+         * A finished search returns the same solution as `best_knapsack()`, which may not be the
+         * first of several equally good ones that we kept.
+         */
+        let keep = if search.stopped {
+            if search.best_value > u32::MAX as u64 {
+                panic!("RecursiveSolver failed: the value of the best solution found overflows");
+            }
+            search.best_keep
+        } else {
+            let mut keep = vec![false; problem.kp_options.len()];
+            for index in taken {
+                keep[index] = true;
+            }
+            keep
+        };
+        let ls_optimal = !search.stopped;
+        LimitedSolution { ls_solution: solution_from_flags(problem, &keep), ls_optimal }
+    }
+}
+
+/// The `DynamicSolver` type -- solves problems with `dynamic_knapsack()`.
+///
+/// This isn't a search, so the limits don't apply and the solution is always optimal.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DynamicSolver;
+
+impl KnapsackSolver for DynamicSolver {
+    fn name(&self) -> &'static str { "dynamic" }
+
+    fn solve_with_limits(&self, problem: KnapsackProblem, _limits: &SolverLimits) -> LimitedSolution {
+        LimitedSolution { ls_solution: dynamic_knapsack(problem), ls_optimal: true }
+    }
+}
+
+/// The `BranchBoundSolver` type -- solves problems with `branch_and_bound_knapsack()`.
+///
+/// Each node counted in `BranchStatistics::bs_nodes_visited` counts towards
+/// `SolverLimits::sl_max_nodes`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BranchBoundSolver;
+
+impl KnapsackSolver for BranchBoundSolver {
+    fn name(&self) -> &'static str { "branch-and-bound" }
+
+    fn solve_with_limits(&self, problem: KnapsackProblem, limits: &SolverLimits) -> LimitedSolution {
+        let (ls_solution, _, ls_optimal) = limited_branch_and_bound(problem, limits);
+        LimitedSolution { ls_solution, ls_optimal }
+    }
+}
+
+/// The `AutoSolver` type -- picks one of the other solvers based on the size of the problem.
+///
+/// Problems with only a few items are solved by `RecursiveSolver`, problems whose dynamic
+/// programming table would be a reasonable size by `DynamicSolver`, and anything else by
+/// `BranchBoundSolver`.
+///
+/// ```
+/// use rust_algorithms::knapsack_problem::{AutoSolver, KnapsackSolver, KnapsackProblem, Item};
+///
+/// let problem = KnapsackProblem {
+///     kp_capacity: 4_000_000_000,
+///     kp_options: vec![Item { item_weight: 3_000_000_000, item_value: 10 }; 20],
+/// };
+/// assert_eq!(AutoSolver.choose(&problem).name(), "branch-and-bound");
+/// assert_eq!(AutoSolver.solve(problem).ks_value, 10);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AutoSolver;
+
+impl AutoSolver {
+    /// The solver that would be used for `problem`.
+    pub fn choose(&self, problem: &KnapsackProblem) -> &'static dyn KnapsackSolver {
+        let cells = table_width(problem).saturating_mul(problem.kp_options.len() + 1);
+        if problem.kp_options.len() <= RECURSIVE_ITEMS {
+            &RecursiveSolver
+        } else if cells <= DYNAMIC_CELLS {
+            &DynamicSolver
+        } else {
+            &BranchBoundSolver
+        }
+    }
+}
+
+impl KnapsackSolver for AutoSolver {
+    fn name(&self) -> &'static str { "auto" }
+
+    fn solve_with_limits(&self, problem: KnapsackProblem, limits: &SolverLimits) -> LimitedSolution {
+        let solver = self.choose(&problem);
+        solver.solve_with_limits(problem, limits)
+    }
+}
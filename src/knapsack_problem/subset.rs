@@ -0,0 +1,196 @@
+/*
+ * Problems about hitting a total weight exactly, rather than getting as much value as possible.
+ *
+ * Subset-sum is a knapsack problem where every item is worth its weight: the best value is the
+ * heaviest filling that fits, and the target can be hit exactly if and only if that's the target.
+ * So we hand it straight to `dynamic_knapsack()`'s table.
+ *
+ * Making change is the same with unlimited copies of each coin, and fewest coins rather than most
+ * value, so it gets its own (one dimensional) table of the fewest coins to make each amount.
+ *
+ * Counting the ways uses the same tables, but adds up the number of ways to reach each total
+ * instead of taking the best.
+ * The counts can get very large (up to 2^n for subsets), so they're `u128` and saturate.
+ *
+ * None of the tables need to go past a total that can't be reached: subsets can't add up to more
+ * than all the items, and coins only make multiples of their greatest common divisor, so with
+ * coins the tables have a column for each multiple rather than for every amount.
+ */
+
+use super::{Item, KnapsackProblem, KnapsackSolution, solution_from_flags};
+use super::dynamic::dynamic_choice;
+use super::quantity::gcd;
+
+/// Returns a subset of `options` whose weights add up to exactly `target`, or `None` if there
+/// isn't one.
+///
+/// The values of the items are ignored, except that they're added up in the solution as usual.
+/// This takes O(n * target) time and memory.
+///
+/// ```
+/// use rust_algorithms::knapsack_problem::{subset_sum, Item};
+///
+/// let options = vec![Item { item_weight: 5, item_value: 0 },
+///                    Item { item_weight: 7, item_value: 0 },
+///                    Item { item_weight: 4, item_value: 0 }];
+/// let solution = subset_sum(options.clone(), 9).unwrap();
+/// assert_eq!(solution.ks_items, vec![Item { item_weight: 5, item_value: 0 },
+///                                    Item { item_weight: 4, item_value: 0 }]);
+/// assert_eq!(solution.ks_capacity, 0);
+/// assert!(subset_sum(options, 8).is_none());
+/// ```
+pub fn subset_sum(options: Vec<Item>, target: u32)
+    -> Option<KnapsackSolution> {
+    let by_weight = KnapsackProblem {
+        kp_capacity: target,
+        kp_options: options.iter()
+            .map(|item| Item { item_weight: item.item_weight, item_value: item.item_weight as u64 })
+            .collect(),
+    };
+    let keep = dynamic_choice(&by_weight).expect("values are weights, which can't add up past the target");
+    let solution = solution_from_flags(KnapsackProblem { kp_capacity: target, kp_options: options }, &keep);
+    if solution.ks_weight == target { Some(solution) } else { None }
+}
+
+/// Returns the number of different subsets of `options` whose weights add up to exactly `target`.
+///
+/// Items that happen to be equal still count as different, and the count saturates at
+/// `u128::MAX`.
+/// This takes O(n * min(target, total weight)) time and O(min(target, total weight)) memory.
+///
+/// ```
+/// use rust_algorithms::knapsack_problem::{count_subset_sums, Item};
+///
+/// let options = vec![Item { item_weight: 2, item_value: 0 },
+///                    Item { item_weight: 3, item_value: 0 },
+///                    Item { item_weight: 5, item_value: 0 }];
+/// assert_eq!(count_subset_sums(&options, 5), 2);
+/// assert_eq!(count_subset_sums(&options, 0), 1);
+/// assert_eq!(count_subset_sums(&options, 4), 0);
+/// ```
+pub fn count_subset_sums(options: &[Item], target: u32)
+    -> u128 {
+    let total_weight = options.iter().fold(0u64, |acc, item| acc + item.item_weight as u64);
+    if target as u64 > total_weight {
+        return 0;
+    }
+    let mut ways = vec![0u128; target as usize + 1];
+    ways[0] = 1;
+    for item in options {
+        let weight = item.item_weight as usize;
+        for total in (weight..ways.len()).rev() {
+            ways[total] = ways[total].saturating_add(ways[total - weight]);
+        }
+    }
+    ways[target as usize]
+}
+
+/*
+ * The greatest common divisor of the (non-zero) coins that are no more than `amount`, and how
+ * many of it make `amount`, or `None` if `amount` isn't a multiple of it and so can't be made.
+ * With no such coins the divisor is zero, and only an amount of zero can be made.
+ */
+fn coin_columns(coins: &[u32], amount: u32) -> Option<(u32, usize)> {
+    let step = coins.iter()
+        .filter(|&&coin| coin <= amount)
+        .fold(0, |acc, &coin| gcd(acc, coin));
+    if step == 0 {
+        return if amount == 0 { Some((0, 0)) } else { None };
+    }
+    let columns = amount / step;
+    if columns * step == amount { Some((step, columns as usize)) } else { None }
+}
+
+/// Returns how many of each of `coins` to use to make exactly `amount` with as few coins as
+/// possible, or `None` if it can't be done.
+///
+/// There are unlimited copies of each coin.
+/// The counts are in the same order as `coins`, and when there are several ways to use the fewest
+/// coins any one of them may be returned.
+/// This takes O(coins * amount / g) time and O(amount / g) memory, where `g` is the greatest
+/// common divisor of the coins.
+///
+/// ```
+/// use rust_algorithms::knapsack_problem::make_change;
+///
+/// // Greedily taking the biggest coin gives 4 + 1 + 1, but 3 + 3 is better.
+/// assert_eq!(make_change(&[1, 3, 4], 6), Some(vec![0, 2, 0]));
+/// assert_eq!(make_change(&[2, 5], 3), None);
+/// ```
+pub fn make_change(coins: &[u32], amount: u32)
+    -> Option<Vec<u32>> {
+    let mut counts = vec![0u32; coins.len()];
+    let (step, columns) = match coin_columns(coins, amount) {
+        Some((_, 0))  => return Some(counts),
+        Some(columns) => columns,
+        None          => return None,
+    };
+
+    /*
+     * `fewest[c]` is the fewest coins that make `c * step`, and `last[c]` the index of a coin
+     * used for it, so following `last` back from `columns` gives the coins.
+     */
+    let mut fewest = vec![u32::MAX; columns + 1];
+    let mut last   = vec![0usize; columns + 1];
+    fewest[0] = 0;
+    for total in 1..columns + 1 {
+        for (index, &coin) in coins.iter().enumerate() {
+            if coin == 0 || coin > amount {
+                continue;
+            }
+            let coin = (coin / step) as usize;
+            if coin > total || fewest[total - coin] == u32::MAX {
+                continue;
+            }
+            if fewest[total - coin] + 1 < fewest[total] {
+                fewest[total] = fewest[total - coin] + 1;
+                last[total]   = index;
+            }
+        }
+    }
+    if fewest[columns] == u32::MAX {
+        return None;
+    }
+
+    let mut remaining = columns;
+    while remaining > 0 {
+        counts[last[remaining]] += 1;
+        remaining -= (coins[last[remaining]] / step) as usize;
+    }
+    Some(counts)
+}
+
+/// Returns the number of different ways to make exactly `amount` from unlimited copies of
+/// `coins`, where the order of the coins doesn't matter.
+///
+/// The count saturates at `u128::MAX`.
+///
+/// # Panics
+/// If a coin is zero, as then there would be infinitely many ways.
+///
+/// ```
+/// use rust_algorithms::knapsack_problem::count_change;
+///
+/// // 5, 2 + 2 + 1, 2 + 1 + 1 + 1, 1 + 1 + 1 + 1 + 1
+/// assert_eq!(count_change(&[1, 2, 5], 5), 4);
+/// assert_eq!(count_change(&[2], 3), 0);
+/// ```
+pub fn count_change(coins: &[u32], amount: u32)
+    -> u128 {
+    if coins.contains(&0) {
+        panic!("count_change() given a coin of zero, which can be used any number of times");
+    }
+    let (step, columns) = match coin_columns(coins, amount) {
+        Some(columns) => columns,
+        None          => return 0,
+    };
+    let mut ways = vec![0u128; columns + 1];
+    ways[0] = 1;
+    for &coin in coins.iter().filter(|&&coin| coin <= amount) {
+        let coin = (coin / step) as usize;
+        for total in coin..ways.len() {
+            ways[total] = ways[total].saturating_add(ways[total - coin]);
+        }
+    }
+    ways[columns]
+}
@@ -0,0 +1,93 @@
+use knapsack_problem::*;
+use test_utils::{random_vector, seeded_rng};
+use test_utils::rand::Rng;
+use super::light_items;
+
+fn check_consistent(approximate: &ApproximateSolution, problem: &KnapsackProblem) {
+    let solution = &approximate.as_solution;
+    assert!(solution.option_indices(&problem.kp_options).is_some());
+    assert_eq!(solution.ks_weight, solution.ks_items.iter().map(|item| item.item_weight).sum::<u32>());
+    assert_eq!(solution.ks_value, solution.ks_items.iter().map(|item| item.item_value).sum::<u32>());
+    assert_eq!(solution.ks_weight + solution.ks_capacity, problem.kp_capacity);
+    assert!(approximate.as_upper_bound >= solution.ks_value as u64);
+}
+
+#[test]
+fn approximate_handles_nothing_useful() {
+    let problem = KnapsackProblem {
+        kp_capacity: 3,
+        kp_options: vec![Item { item_weight: 4, item_value: 10 }, Item { item_weight: 1, item_value: 0 }],
+    };
+    assert_eq!(approximate_knapsack(problem, 0.5), ApproximateSolution {
+        as_solution: KnapsackSolution { ks_weight: 0, ks_capacity: 3, ks_value: 0, ks_items: Vec::new() },
+        as_upper_bound: 0,
+    });
+}
+
+#[test]
+fn approximate_within_epsilon() {
+    let mut rng = seeded_rng();
+    let epsilon = [0.5, 0.2, 0.05][rng.gen::<usize>() % 3];
+    let problem = KnapsackProblem { kp_capacity: rng.gen::<u32>() % 2000, kp_options: light_items(60, 100) };
+    println!("Epsilon: {}, Problem: {:?}", epsilon, problem);
+
+    let approximate = approximate_knapsack(problem.clone(), epsilon);
+    check_consistent(&approximate, &problem);
+    let optimal = dynamic_knapsack(problem).ks_value;
+    assert!(approximate.as_solution.ks_value as f64 >= (1.0 - epsilon) * optimal as f64);
+    assert!(approximate.as_upper_bound >= optimal as u64);
+}
+
+#[test]
+fn approximate_large_values() {
+    /*
+     * Values and weights too large for any table indexed by either of them, but the
+     * approximation only needs a table sized by the number of items and epsilon.
+     */
+    let mut rng = seeded_rng();
+    let item_options: Vec<Item> = random_vector::<(u32, u32)>(200).into_iter()
+        .map(|(weight, value)| Item { item_weight: weight >> 8, item_value: value >> 8 })
+        .collect();
+    let problem = KnapsackProblem { kp_capacity: rng.gen::<u32>() >> 1, kp_options: item_options };
+    let approximate = approximate_knapsack(problem.clone(), 0.1);
+    check_consistent(&approximate, &problem);
+    let gap = approximate.as_upper_bound - approximate.as_solution.ks_value as u64;
+    assert!(gap <= (0.1 * approximate.as_solution.ks_value as f64 / 0.9).ceil() as u64 + 1);
+}
+
+#[test]
+#[should_panic(expected = "not positive")]
+fn approximate_rejects_bad_epsilon() {
+    approximate_knapsack(KnapsackProblem { kp_capacity: 1, kp_options: Vec::new() }, 0.0);
+}
+
+#[test]
+fn heuristics_bounded_by_optimal() {
+    let mut rng = seeded_rng();
+    let problem = KnapsackProblem { kp_capacity: rng.gen::<u32>() % 2000, kp_options: light_items(40, 100) };
+    println!("Problem: {:?}", problem);
+
+    let optimal  = dynamic_knapsack(problem.clone()).ks_value;
+    let greedy   = greedy_knapsack(problem.clone());
+    let best     = greedy_best_item_knapsack(problem.clone());
+    let improved = local_search_knapsack(problem.clone());
+    for approximate in &[&greedy, &best, &improved] {
+        check_consistent(approximate, &problem);
+        assert!(approximate.as_solution.ks_value <= optimal);
+        assert!(approximate.as_upper_bound >= optimal as u64);
+    }
+    assert!(best.as_solution.ks_value >= greedy.as_solution.ks_value);
+    assert!(improved.as_solution.ks_value >= best.as_solution.ks_value);
+    assert!(2 * best.as_solution.ks_value >= optimal);
+}
+
+#[test]
+fn heuristics_greedy_can_be_bad() {
+    let problem = KnapsackProblem {
+        kp_capacity: 100,
+        kp_options: vec![Item { item_weight: 1, item_value: 2 }, Item { item_weight: 100, item_value: 100 }],
+    };
+    assert_eq!(greedy_knapsack(problem.clone()).as_solution.ks_value, 2);
+    assert_eq!(greedy_best_item_knapsack(problem.clone()).as_solution.ks_value, 100);
+    assert_eq!(local_search_knapsack(problem).as_solution.ks_value, 100);
+}
@@ -0,0 +1,74 @@
+use knapsack_problem::*;
+use test_utils::{random_vector, seeded_rng};
+use test_utils::rand::Rng;
+use super::light_items;
+
+#[test]
+fn branch_handles_base_case() {
+    let (knapsack_solution, statistics) = branch_and_bound_knapsack(KnapsackProblem {
+        kp_capacity: 10, kp_options: Vec::new()
+    });
+    assert_eq!(knapsack_solution, KnapsackSolution {
+        ks_weight: 0, ks_value: 0, ks_capacity: 10, ks_items: Vec::new(),
+    });
+    assert_eq!(statistics, BranchStatistics { bs_nodes_visited: 1, bs_nodes_pruned: 0 });
+}
+
+#[test]
+fn branch_takes_weightless() {
+    let item_options = vec![
+        Item { item_weight: 0, item_value: 3 },
+        Item { item_weight: 5, item_value: 1 },
+        Item { item_weight: 0, item_value: 0 },
+    ];
+    let (knapsack_solution, _) = branch_and_bound_knapsack(KnapsackProblem {
+        kp_capacity: 4, kp_options: item_options.clone()
+    });
+    assert_eq!(knapsack_solution, KnapsackSolution {
+        ks_weight: 0, ks_value: 3, ks_capacity: 4, ks_items: vec![item_options[0].clone()],
+    });
+}
+
+#[test]
+fn branch_matches_recursive() {
+    let mut rng = seeded_rng();
+    let item_options: Vec<Item> = random_vector(16);
+    let capacity = rng.gen::<u32>() % (1 << 20);
+    println!("Capacity: {}, Items: {:?}", capacity, item_options);
+
+    let problem = KnapsackProblem { kp_capacity: capacity, kp_options: item_options };
+    let (knapsack_solution, _) = branch_and_bound_knapsack(problem.clone());
+    let recursive_solution = best_knapsack(problem);
+    assert_eq!(knapsack_solution.ks_value, recursive_solution.ks_value);
+    assert!(knapsack_solution.ks_weight + knapsack_solution.ks_capacity == capacity);
+}
+
+#[test]
+fn branch_matches_dynamic() {
+    let mut rng = seeded_rng();
+    let item_options = light_items(200, 50);
+    let capacity = rng.gen::<u32>() % 2000;
+    println!("Capacity: {}, Items: {:?}", capacity, item_options);
+
+    let problem = KnapsackProblem { kp_capacity: capacity, kp_options: item_options };
+    let (knapsack_solution, statistics) = branch_and_bound_knapsack(problem.clone());
+    assert_eq!(knapsack_solution.ks_value, dynamic_knapsack(problem).ks_value);
+    assert!(knapsack_solution.ks_weight <= capacity);
+    assert!(statistics.bs_nodes_pruned <= statistics.bs_nodes_visited);
+}
+
+#[test]
+fn branch_sorts_past_worthless() {
+    let items = |pairs: &[(u32, u32)]| -> Vec<Item> {
+        pairs.iter().map(|&(item_weight, item_value)| Item { item_weight, item_value }).collect()
+    };
+    let problems = vec![
+        KnapsackProblem { kp_capacity: 0, kp_options: items(&[(6, 0), (0, 0), (18, 2), (0, 12)]) },
+        KnapsackProblem { kp_capacity: 20, kp_options: items(&[(14, 11), (16, 15), (19, 9), (13, 1), (8, 14),
+                                                               (0, 0), (11, 14), (0, 0), (19, 14)]) },
+    ];
+    for (problem, value) in problems.into_iter().zip(vec![12, 28]) {
+        let (knapsack_solution, _) = branch_and_bound_knapsack(problem.clone());
+        assert_eq!(knapsack_solution.ks_value, value, "Problem: {:?}", problem);
+    }
+}
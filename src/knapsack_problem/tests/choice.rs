@@ -0,0 +1,66 @@
+use knapsack_problem::*;
+use test_utils::seeded_rng;
+use test_utils::rand::Rng;
+use super::light_items;
+
+/* Try every combination of choices, only usable for a few small groups. */
+fn exhaustive_value(groups: &[Vec<Item>], capacity: u32) -> u32 {
+    let (group, rest) = match groups.split_first() {
+        Some(split) => split,
+        None => return 0,
+    };
+    group.iter()
+        .filter(|item| item.item_weight <= capacity)
+        .map(|item| item.item_value + exhaustive_value(rest, capacity - item.item_weight))
+        .fold(exhaustive_value(rest, capacity), |best, value| best.max(value))
+}
+
+fn random_groups(max_groups: usize, max_size: usize) -> Vec<Vec<Item>> {
+    let mut rng = seeded_rng();
+    (0..(rng.gen::<usize>() % (max_groups + 1)))
+        .map(|_| light_items(max_size, 40))
+        .collect()
+}
+
+#[test]
+fn choice_handles_empty_groups() {
+    let solution = multiple_choice_knapsack(ChoiceProblem {
+        cp_capacity: 5,
+        cp_groups: vec![Vec::new(), vec![Item { item_weight: 6, item_value: 2 }]],
+    });
+    assert_eq!(solution, ChoiceSolution {
+        cs_weight: 0, cs_capacity: 5, cs_value: 0, cs_choices: vec![None, None],
+    });
+}
+
+#[test]
+fn choice_matches_exhaustive() {
+    let mut rng = seeded_rng();
+    let groups = random_groups(6, 4);
+    let capacity = rng.gen::<u32>() % 120;
+    println!("Capacity: {}, Groups: {:?}", capacity, groups);
+
+    let solution = multiple_choice_knapsack(ChoiceProblem { cp_capacity: capacity, cp_groups: groups.clone() });
+    assert_eq!(solution.cs_value, exhaustive_value(&groups, capacity));
+    assert_eq!(solution.cs_choices.len(), groups.len());
+    let chosen: Vec<&Item> = solution.cs_choices.iter().zip(&groups)
+        .filter_map(|(choice, group)| choice.map(|index| &group[index]))
+        .collect();
+    assert_eq!(solution.cs_weight, chosen.iter().map(|item| item.item_weight).sum::<u32>());
+    assert_eq!(solution.cs_value, chosen.iter().map(|item| item.item_value).sum::<u32>());
+    assert_eq!(solution.cs_weight + solution.cs_capacity, capacity);
+}
+
+#[test]
+fn choice_singletons_match_dynamic() {
+    /* With one item per group, this is the normal knapsack problem. */
+    let mut rng = seeded_rng();
+    let item_options = light_items(40, 100);
+    let capacity = rng.gen::<u32>() % 1000;
+    let solution = multiple_choice_knapsack(ChoiceProblem {
+        cp_capacity: capacity,
+        cp_groups: item_options.iter().map(|item| vec![item.clone()]).collect(),
+    });
+    let expected = dynamic_knapsack(KnapsackProblem { kp_capacity: capacity, kp_options: item_options });
+    assert_eq!(solution.cs_value, expected.ks_value);
+}
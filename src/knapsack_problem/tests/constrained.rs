@@ -0,0 +1,185 @@
+use knapsack_problem::*;
+use test_utils::seeded_rng;
+use test_utils::rand::Rng;
+use super::light_items;
+
+/* Check every subset of the items that keeps to the constraints, only usable for a few items. */
+fn exhaustive_value(problem: &KnapsackProblem, constraints: &KnapsackConstraints) -> u32 {
+    let count = problem.kp_options.len();
+    (0..(1u32 << count))
+        .filter(|subset| {
+            let taken = |index: usize| subset & (1 << index) != 0;
+            constraints.kc_requires.iter().all(|&(item, required)| !taken(item) || taken(required)) &&
+                constraints.kc_conflicts.iter().all(|&(left, right)| !(taken(left) && taken(right)))
+        })
+        .filter_map(|subset| {
+            let chosen: Vec<&Item> = (0..count)
+                .filter(|index| subset & (1 << index) != 0)
+                .map(|index| &problem.kp_options[index])
+                .collect();
+            let weight: u32 = chosen.iter().map(|item| item.item_weight).sum();
+            if weight <= problem.kp_capacity {
+                Some(chosen.iter().map(|item| item.item_value).sum())
+            } else {
+                None
+            }
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/* A problem with a few distinct items, so that the indices of the chosen items are unambiguous. */
+fn distinct_problem() -> KnapsackProblem {
+    let mut rng = seeded_rng();
+    let item_options: Vec<Item> = light_items(12, 40).into_iter()
+        .enumerate()
+        .map(|(index, item)| Item { item_value: (item.item_value % 100) * 16 + index as u32, ..item })
+        .collect();
+    KnapsackProblem { kp_capacity: rng.gen::<u32>() % 150, kp_options: item_options }
+}
+
+#[test]
+fn constrained_without_constraints() {
+    let mut rng = seeded_rng();
+    let problem = KnapsackProblem { kp_capacity: rng.gen::<u32>() % 1000, kp_options: light_items(40, 100) };
+    println!("Problem: {:?}", problem);
+    let solution = constrained_knapsack(problem.clone(), &KnapsackConstraints::default()).unwrap();
+    assert_eq!(solution.ks_value, dynamic_knapsack(problem).ks_value);
+}
+
+#[test]
+fn constrained_matches_exhaustive() {
+    let mut rng = seeded_rng();
+    let problem = distinct_problem();
+    let count = problem.kp_options.len();
+    let mut constraints = KnapsackConstraints::default();
+    if count > 1 {
+        /*
+         * Conflicts only join items that aren't part of any requirement, so the constraints can
+         * never contradict each other.
+         */
+        for item in 1..count {
+            if rng.gen::<u32>() % 3 == 0 {
+                constraints.kc_requires.push((item, rng.gen::<usize>() % item));
+            }
+        }
+        for _ in 0..(rng.gen::<usize>() % 4) {
+            let (left, right) = (rng.gen::<usize>() % count, rng.gen::<usize>() % count);
+            let free = |index: usize| !constraints.kc_requires.iter()
+                .any(|&(item, required)| item == index || required == index);
+            if left != right && free(left) && free(right) {
+                constraints.kc_conflicts.push((left, right));
+            }
+        }
+    }
+    println!("Problem: {:?}\nConstraints: {:?}", problem, constraints);
+
+    let solution = constrained_knapsack(problem.clone(), &constraints).unwrap();
+    assert_eq!(solution.ks_value, exhaustive_value(&problem, &constraints));
+    let indices = solution.option_indices(&problem.kp_options).unwrap();
+    for &(item, required) in &constraints.kc_requires {
+        assert!(!indices.contains(&item) || indices.contains(&required));
+    }
+    for &(left, right) in &constraints.kc_conflicts {
+        assert!(!(indices.contains(&left) && indices.contains(&right)));
+    }
+}
+
+#[test]
+fn constrained_reports_errors() {
+    let problem = KnapsackProblem {
+        kp_capacity: 10,
+        kp_options: vec![Item { item_weight: 1, item_value: 1 }; 4],
+    };
+    assert_eq!(constrained_knapsack(problem.clone(), &KnapsackConstraints {
+        kc_requires: vec![(0, 4)], kc_conflicts: Vec::new(),
+    }), Err(KnapsackError::UnknownOption(4)));
+    assert_eq!(constrained_knapsack(problem.clone(), &KnapsackConstraints {
+        kc_requires: vec![(0, 1), (1, 2)], kc_conflicts: vec![(3, 2), (2, 0)],
+    }), Err(KnapsackError::Contradiction(0)));
+    assert!(constrained_knapsack(problem, &KnapsackConstraints {
+        kc_requires: vec![(0, 1), (1, 0)], kc_conflicts: vec![(1, 2)],
+    }).is_ok());
+}
+
+#[test]
+fn constrained_sorts_past_worthless() {
+    let problem = KnapsackProblem {
+        kp_capacity: 20,
+        kp_options: vec![
+            Item { item_weight: 14, item_value: 11 }, Item { item_weight: 16, item_value: 15 },
+            Item { item_weight: 19, item_value: 9 }, Item { item_weight: 13, item_value: 1 },
+            Item { item_weight: 8, item_value: 14 }, Item { item_weight: 0, item_value: 0 },
+            Item { item_weight: 11, item_value: 14 }, Item { item_weight: 0, item_value: 0 },
+            Item { item_weight: 19, item_value: 14 },
+        ],
+    };
+    let solution = constrained_knapsack(problem, &KnapsackConstraints::default()).unwrap();
+    assert_eq!(solution.ks_value, 28);
+}
+
+#[test]
+fn constrained_required_item_conflicts() {
+    let problem = KnapsackProblem {
+        kp_capacity: 10,
+        kp_options: vec![Item { item_weight: 4, item_value: 6 },
+                         Item { item_weight: 3, item_value: 1 },
+                         Item { item_weight: 3, item_value: 5 },
+                         Item { item_weight: 3, item_value: 2 }],
+    };
+    /* Taking the first item means taking the second, which rules out the third. */
+    let constraints = KnapsackConstraints { kc_requires: vec![(0, 1)], kc_conflicts: vec![(1, 2)] };
+    let solution = constrained_knapsack(problem.clone(), &constraints).unwrap();
+    assert_eq!(solution.ks_value, 9);
+    assert_eq!(solution.option_indices(&problem.kp_options), Some(vec![0, 1, 3]));
+
+    /* The second item requires the first, and they conflict. */
+    let constraints = KnapsackConstraints {
+        kc_requires: vec![(3, 2), (2, 1), (1, 0)], kc_conflicts: vec![(0, 1)],
+    };
+    assert_eq!(constrained_knapsack(problem.clone(), &constraints), Err(KnapsackError::Contradiction(1)));
+    /* Only the last item requires both of them, one through a chain. */
+    let constraints = KnapsackConstraints {
+        kc_requires: vec![(3, 2), (2, 1), (3, 0)], kc_conflicts: vec![(0, 1)],
+    };
+    assert_eq!(constrained_knapsack(problem, &constraints), Err(KnapsackError::Contradiction(3)));
+}
+
+#[test]
+fn constrained_mixed_matches_exhaustive() {
+    let mut rng = seeded_rng();
+    let problem = distinct_problem();
+    let count = problem.kp_options.len();
+    let mut constraints = KnapsackConstraints::default();
+    for _ in 0..count {
+        let (left, right) = (rng.gen::<usize>() % count, rng.gen::<usize>() % count);
+        if left == right {
+            continue;
+        }
+        if rng.gen::<bool>() {
+            constraints.kc_requires.push((left, right));
+        } else {
+            constraints.kc_conflicts.push((left, right));
+        }
+    }
+    println!("Problem: {:?}\nConstraints: {:?}", problem, constraints);
+
+    /* An item is a contradiction if no subset that keeps to the constraints takes it. */
+    let allowed: Vec<u32> = (0..(1u32 << count))
+        .filter(|subset| {
+            let taken = |index: usize| subset & (1 << index) != 0;
+            constraints.kc_requires.iter().all(|&(item, required)| !taken(item) || taken(required)) &&
+                constraints.kc_conflicts.iter().all(|&(left, right)| !(taken(left) && taken(right)))
+        })
+        .collect();
+    let contradiction = (0..count).find(|&index| allowed.iter().all(|subset| subset & (1 << index) == 0));
+    match contradiction {
+        Some(index) => {
+            assert_eq!(constrained_knapsack(problem, &constraints), Err(KnapsackError::Contradiction(index)));
+        },
+        None => {
+            let solution = constrained_knapsack(problem.clone(), &constraints).unwrap();
+            assert_eq!(solution.ks_value, exhaustive_value(&problem, &constraints));
+        },
+    }
+}
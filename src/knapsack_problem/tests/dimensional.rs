@@ -0,0 +1,106 @@
+use knapsack_problem::*;
+use test_utils::seeded_rng;
+use test_utils::rand::Rng;
+use super::light_items;
+
+/* Check every subset of the items, only usable for a handful of items. */
+fn exhaustive_value(problem: &DimensionalProblem) -> u32 {
+    let count = problem.dp_options.len();
+    (0..(1u32 << count))
+        .filter_map(|subset| {
+            let chosen: Vec<&DimensionalItem> = (0..count)
+                .filter(|index| subset & (1 << index) != 0)
+                .map(|index| &problem.dp_options[index])
+                .collect();
+            let fits = problem.dp_capacities.iter().enumerate().all(|(dimension, &capacity)| {
+                chosen.iter().map(|item| item.di_usage[dimension]).sum::<u32>() <= capacity
+            });
+            if fits { Some(chosen.iter().map(|item| item.di_value).sum()) } else { None }
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+#[test]
+fn dimensional_handles_no_dimensions() {
+    let item_options = vec![
+        DimensionalItem { di_usage: Vec::new(), di_value: 3 },
+        DimensionalItem { di_usage: Vec::new(), di_value: 0 },
+    ];
+    let solution = dimensional_knapsack(DimensionalProblem {
+        dp_capacities: Vec::new(), dp_options: item_options.clone(),
+    });
+    assert_eq!(solution, DimensionalSolution {
+        ds_usage: Vec::new(), ds_capacities: Vec::new(), ds_value: 3,
+        ds_items: vec![item_options[0].clone()],
+    });
+}
+
+#[test]
+fn dimensional_matches_one_dimension() {
+    let mut rng = seeded_rng();
+    let item_options = light_items(40, 100);
+    let capacity = rng.gen::<u32>() % 1000;
+    println!("Capacity: {}, Items: {:?}", capacity, item_options);
+
+    let solution = dimensional_knapsack(DimensionalProblem {
+        dp_capacities: vec![capacity],
+        dp_options: item_options.iter()
+            .map(|item| DimensionalItem { di_usage: vec![item.item_weight], di_value: item.item_value })
+            .collect(),
+    });
+    let expected = dynamic_knapsack(KnapsackProblem { kp_capacity: capacity, kp_options: item_options });
+    assert_eq!(solution.ds_value, expected.ks_value);
+    assert_eq!(solution.ds_usage, vec![capacity - solution.ds_capacities[0]]);
+}
+
+#[test]
+fn dimensional_matches_exhaustive() {
+    let mut rng = seeded_rng();
+    let dimensions = rng.gen::<usize>() % 4 + 1;
+    let count = rng.gen::<usize>() % 13;
+    let problem = DimensionalProblem {
+        dp_capacities: (0..dimensions).map(|_| rng.gen::<u32>() % 100).collect(),
+        dp_options: (0..count)
+            .map(|_| DimensionalItem {
+                di_usage: (0..dimensions).map(|_| rng.gen::<u32>() % 40).collect(),
+                di_value: rng.gen::<u32>() % 50,
+            })
+            .collect(),
+    };
+    println!("Problem: {:?}", problem);
+
+    let solution = dimensional_knapsack(problem.clone());
+    assert_eq!(solution.ds_value, exhaustive_value(&problem));
+    for dimension in 0..dimensions {
+        let used: u32 = solution.ds_items.iter().map(|item| item.di_usage[dimension]).sum();
+        assert_eq!(solution.ds_usage[dimension], used);
+        assert_eq!(solution.ds_capacities[dimension] + used, problem.dp_capacities[dimension]);
+    }
+}
+
+#[test]
+#[should_panic(expected = "dimensions")]
+fn dimensional_rejects_mismatched() {
+    dimensional_knapsack(DimensionalProblem {
+        dp_capacities: vec![1, 2],
+        dp_options: vec![DimensionalItem { di_usage: vec![1], di_value: 1 }],
+    });
+}
+
+#[test]
+fn dimensional_sorts_past_worthless() {
+    /*
+     * Items using nothing and worth nothing used to compare equal to everything when sorting by
+     * ratio, and only the order the search visits items in kept them from upsetting the bound.
+     */
+    let usage_values = [(14, 11), (16, 15), (19, 9), (13, 1), (8, 14), (0, 0), (11, 14), (0, 0), (19, 14)];
+    let problem = DimensionalProblem {
+        dp_capacities: vec![20, 20],
+        dp_options: usage_values.iter()
+            .map(|&(usage, value)| DimensionalItem { di_usage: vec![usage, usage], di_value: value })
+            .collect(),
+    };
+    assert_eq!(dimensional_knapsack(problem.clone()).ds_value, 28);
+    assert_eq!(exhaustive_value(&problem), 28);
+}
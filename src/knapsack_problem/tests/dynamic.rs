@@ -0,0 +1,155 @@
+use knapsack_problem::*;
+use test_utils::seeded_rng;
+use test_utils::rand::Rng;
+use super::light_items;
+
+const MAX_RECURSIVE_SIZE: usize = 16;
+
+#[test]
+fn dynamic_handles_base_case() {
+    let knapsack_solution = dynamic_knapsack(KnapsackProblem {
+        kp_capacity: 0, kp_options: Vec::new()
+    });
+    assert_eq!(knapsack_solution, KnapsackSolution {
+        ks_weight: 0, ks_value: 0, ks_capacity: 0, ks_items: Vec::new(),
+    });
+}
+
+#[test]
+fn dynamic_handles_huge_capacity() {
+    /*
+     * The table should be clamped to the total weight of the items, otherwise this would try to
+     * allocate 16GB.
+     */
+    let item_options = vec![
+        Item { item_weight: 12, item_value: 4 },
+        Item { item_weight: 1, item_value: 0 },
+        Item { item_weight: 3, item_value: 2 },
+    ];
+    let knapsack_solution = dynamic_knapsack(KnapsackProblem {
+        kp_capacity: u32::MAX, kp_options: item_options.clone()
+    });
+    assert_eq!(knapsack_solution, KnapsackSolution {
+        ks_weight: 15,
+        ks_value: 6,
+        ks_capacity: u32::MAX - 15,
+        ks_items: vec![item_options[0].clone(), item_options[2].clone()],
+    });
+}
+
+#[test]
+fn dynamic_matches_recursive() {
+    let mut rng = seeded_rng();
+    let item_options = light_items(MAX_RECURSIVE_SIZE, 100);
+    let capacity = rng.gen::<u32>() % 800;
+    println!("Capacity: {}, Items: {:?}", capacity, item_options);
+
+    let problem = KnapsackProblem { kp_capacity: capacity, kp_options: item_options };
+    assert_eq!(dynamic_knapsack(problem.clone()), best_knapsack(problem));
+}
+
+#[test]
+fn dynamic_overflows_like_recursive() {
+    /* Values big enough that a few of them together overflow, but any one of them fits. */
+    let mut rng = seeded_rng();
+    let item_options: Vec<Item> = light_items(MAX_RECURSIVE_SIZE, 100).into_iter()
+        .map(|item| Item { item_value: rng.gen::<u32>() / 4, ..item })
+        .collect();
+    let capacity = rng.gen::<u32>() % 400;
+    println!("Capacity: {}, Items: {:?}", capacity, item_options);
+
+    let problem = KnapsackProblem { kp_capacity: capacity, kp_options: item_options };
+    match try_best_knapsack(problem.clone()) {
+        Ok(solution) => assert_eq!(try_dynamic_knapsack(problem), Ok(solution)),
+        Err(_) => assert!(try_dynamic_knapsack(problem).is_err()),
+    }
+}
+
+#[test]
+#[should_panic(expected = "value overflowed")]
+fn dynamic_panics_on_overflow() {
+    dynamic_knapsack(KnapsackProblem {
+        kp_capacity: 2,
+        kp_options: vec![Item { item_weight: 1, item_value: u32::MAX },
+                         Item { item_weight: 1, item_value: 1 }],
+    });
+}
+
+#[test]
+fn hirschberg_handles_base_case() {
+    let knapsack_solution = hirschberg_knapsack(KnapsackProblem {
+        kp_capacity: 7, kp_options: Vec::new()
+    });
+    assert_eq!(knapsack_solution, KnapsackSolution {
+        ks_weight: 0, ks_value: 0, ks_capacity: 7, ks_items: Vec::new(),
+    });
+}
+
+#[test]
+fn hirschberg_matches_recursive() {
+    let mut rng = seeded_rng();
+    let item_options = light_items(MAX_RECURSIVE_SIZE, 100);
+    let capacity = rng.gen::<u32>() % 800;
+    println!("Capacity: {}, Items: {:?}", capacity, item_options);
+
+    let problem = KnapsackProblem { kp_capacity: capacity, kp_options: item_options };
+    assert_eq!(hirschberg_knapsack(problem.clone()), best_knapsack(problem));
+}
+
+#[test]
+fn hirschberg_matches_dynamic() {
+    /*
+     * Lots of ties in value and weight, so that this checks the tie-breaking matches on a problem
+     * too large for `best_knapsack()`.
+     */
+    let mut rng = seeded_rng();
+    let item_options: Vec<Item> = (0..200)
+        .map(|_| Item { item_weight: rng.gen::<u32>() % 20, item_value: rng.gen::<u32>() % 10 })
+        .collect();
+    let capacity = rng.gen::<u32>() % 1000;
+    println!("Capacity: {}, Items: {:?}", capacity, item_options);
+
+    let problem = KnapsackProblem { kp_capacity: capacity, kp_options: item_options };
+    assert_eq!(hirschberg_knapsack(problem.clone()), dynamic_knapsack(problem));
+}
+
+#[test]
+#[should_panic(expected = "value overflowed")]
+fn hirschberg_panics_on_overflow() {
+    hirschberg_knapsack(KnapsackProblem {
+        kp_capacity: 2,
+        kp_options: vec![Item { item_weight: 1, item_value: u32::MAX },
+                         Item { item_weight: 1, item_value: 1 }],
+    });
+}
+
+#[test]
+fn batch_handles_no_capacities() {
+    assert!(batch_knapsack(light_items(10, 100), &[]).is_empty());
+}
+
+#[test]
+fn batch_matches_dynamic() {
+    let mut rng = seeded_rng();
+    let item_options = light_items(40, 100);
+    let capacities: Vec<u32> = (0..rng.gen::<usize>() % 20)
+        .map(|_| if rng.gen() { rng.gen::<u32>() % 2000 } else { rng.gen() })
+        .collect();
+    println!("Capacities: {:?}, Items: {:?}", capacities, item_options);
+
+    let solutions = batch_knapsack(item_options.clone(), &capacities);
+    assert_eq!(solutions.len(), capacities.len());
+    for (solution, &capacity) in solutions.into_iter().zip(&capacities) {
+        assert_eq!(solution, dynamic_knapsack(KnapsackProblem {
+            kp_capacity: capacity, kp_options: item_options.clone()
+        }));
+    }
+}
+
+#[test]
+#[should_panic(expected = "value overflowed")]
+fn batch_panics_on_overflow() {
+    /* Only the larger capacity overflows, but the table is filled for it all the same. */
+    batch_knapsack(vec![Item { item_weight: 1, item_value: u32::MAX },
+                        Item { item_weight: 1, item_value: 1 }], &[1, 2]);
+}
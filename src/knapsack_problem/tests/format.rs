@@ -0,0 +1,72 @@
+use knapsack_problem::*;
+use test_utils::seeded_rng;
+use test_utils::rand::Rng;
+use super::light_items;
+
+#[test]
+fn format_round_trip() {
+    let mut rng = seeded_rng();
+    let problem = KnapsackProblem { kp_capacity: rng.gen::<u32>() % 800, kp_options: light_items(20, 100) };
+    println!("Problem: {:?}", problem);
+
+    let text = write_problem(&problem);
+    let read: KnapsackProblem = read_problem(&text).unwrap();
+    assert_eq!((read.kp_capacity, &read.kp_options), (problem.kp_capacity, &problem.kp_options));
+
+    let solution = dynamic_knapsack(problem);
+    assert_eq!(read_solution(&write_solution(&solution)), Ok(solution));
+}
+
+#[test]
+fn format_errors_have_lines() {
+    let error = |text: &str| read_problem::<u32, u32>(text).unwrap_err();
+    assert_eq!(error(""), ParseError { pe_line: 1, pe_message: "expected the capacity".to_string() });
+    assert_eq!(error("\n# nothing\nten\n").pe_line, 3);
+    assert_eq!(error("10\n1 2\n\n3 -4\n").pe_line, 4);
+    assert_eq!(error("10\n1 2 3\n").pe_line, 2);
+    assert_eq!(read_solution::<u32, u32>("3\n2 1\n2 1\n").unwrap_err().pe_line, 3);
+    assert_eq!(read_solution::<u32, u32>("3\n2 1\n\n# heavy\n4294967295 1\n1 1\n").unwrap_err().pe_line, 5);
+    assert_eq!(read_solution::<u32, u32>("3\n1 4294967295\n1 1\n").unwrap_err().pe_line, 3);
+}
+
+#[test]
+fn format_pisinger_errors() {
+    let read_pisinger = read_pisinger::<u32, u32>;
+    let header = "knapPI_1_2_1000_1\nn 2\nc 5\nz 4\ntime 0.00\n";
+    assert_eq!(read_pisinger(&format!("{}1,4,3,1\n-----\n", header)).unwrap_err().pe_line, 7);
+    assert_eq!(read_pisinger(&format!("{}1,4,3,1\n3,5,6,0\n-----\n", header)).unwrap_err().pe_line, 7);
+    assert_eq!(read_pisinger(&format!("{}1,4,3,1\n2,5,6,2\n-----\n", header)).unwrap_err().pe_line, 7);
+    assert_eq!(read_pisinger("knapPI\nc 5\n").unwrap_err().pe_line, 2);
+    assert_eq!(read_pisinger("knapPI\nn 4294967295\nc 5\nz 4\ntime 0.00\n1,4,3,1\n").unwrap_err().pe_line, 7);
+    assert_eq!(read_pisinger(&format!("{}1,4,3,1\n2,5,6,0\n", header)).unwrap_err().pe_line, 8);
+
+    let instances = read_pisinger(&format!("{0}1,4,3,1\n2,5,6,0\n-----\n\n{0}1,1,1,0\n2,2,2,1\n-----\n", header)).unwrap();
+    assert_eq!(instances.len(), 2);
+    assert_eq!(instances[1].pi_taken, vec![false, true]);
+}
+
+#[test]
+fn format_or_library() {
+    let text = "2\n2 1 3\n1 3\n2 5\n5\n1 2 0\n7\n1\n2\n2 3\n";
+    let instances = read_or_library(text).unwrap();
+    assert_eq!(instances.len(), 2);
+    assert_eq!(dimensional_knapsack(instances[0].oi_problem.clone()).ds_value, instances[0].oi_optimum);
+    assert_eq!(instances[1].oi_problem.dp_capacities, vec![2, 3]);
+    assert_eq!(instances[1].oi_problem.dp_options, vec![DimensionalItem { di_usage: vec![1, 2], di_value: 7 }]);
+
+    assert_eq!(read_or_library("1\n2 1 3\n1 x\n").unwrap_err().pe_line, 3);
+    assert_eq!(read_or_library("1\n2 1 3\n1 3\n").unwrap_err().pe_line, 4);
+    /* A count far larger than the file is an error, not an attempt at a huge allocation. */
+    assert_eq!(read_or_library("1\n400000000 1 0\n").unwrap_err().pe_line, 3);
+}
+
+#[test]
+fn format_pisinger_large_coefficients() {
+    let text = "knapPI_3_2_10000000000_1\nn 2\nc 10000000000\nz 9000000000\ntime 0.00\n\
+                1,9000000000,6000000000,1\n2,5000000000,5000000000,0\n-----\n";
+    let instances = read_pisinger::<u64, u64>(text).unwrap();
+    assert_eq!(instances[0].pi_problem.kp_capacity, 10_000_000_000);
+    assert_eq!(instances[0].pi_optimum, 9_000_000_000);
+    assert_eq!(instances[0].pi_problem.kp_options[1], Item { item_weight: 5_000_000_000, item_value: 5_000_000_000 });
+    assert_eq!(read_pisinger::<u32, u32>(text).unwrap_err().pe_line, 3);
+}
@@ -0,0 +1,57 @@
+use knapsack_problem::*;
+use test_utils::seeded_rng;
+use test_utils::rand::Rng;
+use super::light_items;
+
+#[test]
+fn fractional_handles_base_case() {
+    let knapsack_solution = fractional_knapsack(KnapsackProblem {
+        kp_capacity: 3, kp_options: Vec::new()
+    });
+    assert_eq!(knapsack_solution, FractionalSolution {
+        fs_weight: 0, fs_capacity: 3, fs_value: 0.0, fs_items: Vec::new(),
+    });
+}
+
+#[test]
+fn fractional_splits_one_item() {
+    let knapsack_solution = fractional_knapsack(KnapsackProblem {
+        kp_capacity: 6,
+        kp_options: vec![
+            Item { item_weight: 4, item_value: 2 },
+            Item { item_weight: 1, item_value: 0 },
+            Item { item_weight: 2, item_value: 8 },
+            Item { item_weight: 8, item_value: 12 },
+        ],
+    });
+    assert_eq!(knapsack_solution, FractionalSolution {
+        fs_weight: 6,
+        fs_capacity: 0,
+        fs_value: 14.0,
+        fs_items: vec![
+            FractionalItem { fi_item: Item { item_weight: 2, item_value: 8 }, fi_fraction: 1.0 },
+            FractionalItem { fi_item: Item { item_weight: 8, item_value: 12 }, fi_fraction: 0.5 },
+        ],
+    });
+}
+
+#[test]
+fn fractional_bounds_integral() {
+    /*
+     * Relaxing the problem can only make things better, and by less than the value of the one
+     * item that is split.
+     */
+    let mut rng = seeded_rng();
+    let item_options = light_items(100, 100);
+    let capacity = rng.gen::<u32>() % 2000;
+    println!("Capacity: {}, Items: {:?}", capacity, item_options);
+
+    let problem = KnapsackProblem { kp_capacity: capacity, kp_options: item_options };
+    let fractional = fractional_knapsack(problem.clone());
+    let integral = dynamic_knapsack(problem.clone());
+    let most_valuable = problem.kp_options.iter().map(|item| item.item_value).max().unwrap_or(0);
+    assert!(fractional.fs_value >= integral.ks_value as f64);
+    assert!(fractional.fs_value <= (integral.ks_value + most_valuable) as f64);
+    assert!(fractional.fs_items.iter().filter(|item| item.fi_fraction < 1.0).count() <= 1);
+    assert_eq!(fractional.fs_weight + fractional.fs_capacity, capacity);
+}
@@ -0,0 +1,62 @@
+use knapsack_problem::*;
+use test_utils::seeded_rng;
+use test_utils::rand::Rng;
+use super::light_items;
+
+#[test]
+fn incremental_handles_base_case() {
+    let knapsack = IncrementalKnapsack::new(KnapsackProblem {
+        kp_capacity: 4u32, kp_options: Vec::<Item>::new()
+    });
+    assert_eq!(knapsack.solution(), KnapsackSolution {
+        ks_weight: 0, ks_value: 0, ks_capacity: 4, ks_items: Vec::new(),
+    });
+}
+
+#[test]
+fn incremental_huge_capacity() {
+    /* The table should grow with the items, not the capacity. */
+    let mut knapsack = IncrementalKnapsack::new(KnapsackProblem { kp_capacity: u32::MAX, kp_options: Vec::new() });
+    knapsack.add_item(Item { item_weight: 12, item_value: 4 });
+    knapsack.add_item(Item { item_weight: 3, item_value: 2 });
+    assert_eq!(knapsack.solution().ks_value, 6);
+    knapsack.set_capacity(14);
+    assert_eq!(knapsack.solution().ks_value, 4);
+}
+
+#[test]
+fn incremental_matches_dynamic() {
+    let mut rng = seeded_rng();
+    let mut knapsack = IncrementalKnapsack::new(KnapsackProblem {
+        kp_capacity: rng.gen::<u32>() % 500, kp_options: light_items(20, 100)
+    });
+    for _ in 0..50 {
+        match rng.gen::<u32>() % 4 {
+            0 if !knapsack.problem().kp_options.is_empty() => {
+                let index = rng.gen::<usize>() % knapsack.problem().kp_options.len();
+                let expected = knapsack.problem().kp_options[index].clone();
+                assert_eq!(knapsack.remove_item(index), expected);
+            },
+            1 => knapsack.set_capacity(rng.gen::<u32>() % 2000),
+            _ => {
+                let weight = rng.gen::<u32>() % 100;
+                knapsack.add_item(Item { item_weight: weight, item_value: rng.gen::<u16>() as u32 });
+            },
+        }
+        println!("Problem: {:?}", knapsack.problem());
+        assert_eq!(knapsack.solution(), dynamic_knapsack(knapsack.problem().clone()));
+    }
+}
+
+#[test]
+#[should_panic(expected = "value overflowed")]
+fn incremental_panics_on_overflow() {
+    /* Both items only fit together once the capacity grows. */
+    let mut knapsack = IncrementalKnapsack::new(KnapsackProblem {
+        kp_capacity: 1u32,
+        kp_options: vec![Item { item_weight: 1, item_value: u32::MAX },
+                         Item { item_weight: 1, item_value: 1 }],
+    });
+    assert_eq!(knapsack.solution().ks_value, u32::MAX);
+    knapsack.set_capacity(2);
+}
@@ -160,3 +160,128 @@ fn set_check_permutation_independent() {
         assert_eq!(vector_same_set_test(new_permutation, &test_vector), is_same_set);
     }
 }
+
+#[test]
+fn checked_reports_overflow() {
+    let item_options = vec![
+        Item { item_weight: 5, item_value: 1 },
+        Item { item_weight: 1, item_value: u32::MAX - 1 },
+        Item { item_weight: 1, item_value: 1 },
+        Item { item_weight: 1, item_value: 1 },
+    ];
+    let knapsack_problem = KnapsackProblem { kp_capacity: 3, kp_options: item_options };
+    assert_eq!(try_best_knapsack(knapsack_problem), Err(KnapsackError::ValueOverflow(3)));
+}
+
+#[test]
+fn checked_allows_overflowing_options() {
+    /* The total value of all the options overflows, but the best knapsack doesn't. */
+    let item_options = vec![
+        Item { item_weight: 2, item_value: u32::MAX },
+        Item { item_weight: 2, item_value: u32::MAX - 1 },
+    ];
+    let knapsack_problem = KnapsackProblem { kp_capacity: 3, kp_options: item_options.clone() };
+    assert_eq!(try_best_knapsack(knapsack_problem), Ok(KnapsackSolution {
+        ks_weight: 2, ks_value: u32::MAX, ks_capacity: 1, ks_items: vec![item_options[0].clone()],
+    }));
+}
+
+#[test]
+fn checked_matches_unchecked() {
+    let mut rng = seeded_rng();
+    let rand_vec = random_vector(rng.gen::<usize>() % MAX_VECTOR_SIZE);
+    let knapsack_problem = KnapsackProblem { kp_capacity: rng.gen(), kp_options: rand_vec };
+    assert_eq!(try_best_knapsack(knapsack_problem.clone()), Ok(best_knapsack(knapsack_problem)));
+}
+
+#[test]
+#[should_panic(expected = "value overflowed")]
+fn unchecked_panics_on_overflow() {
+    best_knapsack(KnapsackProblem {
+        kp_capacity: 2,
+        kp_options: vec![Item { item_weight: 1, item_value: u32::MAX },
+                         Item { item_weight: 1, item_value: 1 }],
+    });
+}
+
+#[test]
+fn indices_trace_duplicates() {
+    let item_options = vec![
+        Item { item_weight: 1, item_value: 1 },
+        Item { item_weight: 5, item_value: 9 },
+        Item { item_weight: 1, item_value: 1 },
+        Item { item_weight: 1, item_value: 1 },
+    ];
+    let solution = KnapsackSolution {
+        ks_weight: 7, ks_capacity: 0, ks_value: 11,
+        ks_items: vec![item_options[1].clone(), item_options[0].clone(), item_options[0].clone()],
+    };
+    assert_eq!(solution.option_indices(&item_options), Some(vec![1, 0, 2]));
+    assert_eq!(solution.option_indices(&item_options[..3]), Some(vec![1, 0, 2]));
+    assert_eq!(solution.option_indices(&item_options[..2]), None);
+}
+
+#[test]
+fn indices_match_solution() {
+    let mut rng = seeded_rng();
+    let knapsack_problem = KnapsackProblem {
+        kp_capacity: rng.gen::<u32>() % 1000, kp_options: light_items(MAX_VECTOR_SIZE, 100),
+    };
+    let solution = dynamic_knapsack(knapsack_problem.clone());
+    let indices = solution.option_indices(&knapsack_problem.kp_options)
+        .expect("Solution contains items not in the problem");
+
+    let mut sorted_indices = indices.clone();
+    sorted_indices.sort();
+    sorted_indices.dedup();
+    assert_eq!(sorted_indices.len(), indices.len());
+    for (item, index) in solution.ks_items.iter().zip(indices) {
+        assert_eq!(*item, knapsack_problem.kp_options[index]);
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_round_trip() {
+    let problem = KnapsackProblem { kp_capacity: 700, kp_options: light_items(20, 100) };
+    let json = ::serde_json::to_string(&problem).unwrap();
+    let read: KnapsackProblem = ::serde_json::from_str(&json).unwrap();
+    assert_eq!((read.kp_capacity, &read.kp_options), (problem.kp_capacity, &problem.kp_options));
+
+    let solution = dynamic_knapsack(problem);
+    let json = ::serde_json::to_string(&solution).unwrap();
+    assert_eq!(::serde_json::from_str::<KnapsackSolution>(&json).unwrap(), solution);
+
+    let item: Item<u64, f64> = ::serde_json::from_str(r#"{"item_weight": 3, "item_value": 1.5}"#).unwrap();
+    assert_eq!(item, Item { item_weight: 3, item_value: 1.5 });
+}
+
+/*
+ * Random items with weights no larger than `max_weight`.
+ * The dynamic programming solvers build tables as wide as the total weight of the items, so the
+ * 16 bit weights from `Item::rand()` make their tests needlessly slow.
+ */
+fn light_items(max_length: usize, max_weight: u32) -> Vec<Item> {
+    random_vector::<Item>(max_length).into_iter()
+        .map(|item| Item { item_weight: item.item_weight % (max_weight + 1), ..item })
+        .collect()
+}
+
+mod dynamic;
+mod branch_bound;
+mod fractional;
+mod quantity;
+mod dimensional;
+mod multiple;
+mod numeric;
+mod ranked;
+mod constrained;
+mod choice;
+mod approximate;
+mod parallel;
+mod incremental;
+mod format;
+mod solver;
+mod subset;
+mod validate;
+mod sensitivity;
@@ -0,0 +1,104 @@
+use knapsack_problem::*;
+use test_utils::seeded_rng;
+use test_utils::rand::Rng;
+use super::light_items;
+
+/* Try every assignment of items to knapsacks, only usable for a handful of items. */
+fn exhaustive_value(problem: &MultipleProblem) -> u32 {
+    fn assign(items: &[Item], remaining: &mut [u32]) -> u32 {
+        let (item, rest) = match items.split_first() {
+            Some(split) => split,
+            None => return 0,
+        };
+        let mut best = assign(rest, remaining);
+        for bin in 0..remaining.len() {
+            if remaining[bin] >= item.item_weight {
+                remaining[bin] -= item.item_weight;
+                best = best.max(item.item_value + assign(rest, remaining));
+                remaining[bin] += item.item_weight;
+            }
+        }
+        best
+    }
+    assign(&problem.mp_options, &mut problem.mp_capacities.clone())
+}
+
+fn check_consistent(problem: &MultipleProblem, solution: &MultipleSolution) {
+    assert_eq!(solution.ms_knapsacks.len(), problem.mp_capacities.len());
+    let mut all_items = solution.ms_unassigned.clone();
+    for (knapsack, &capacity) in solution.ms_knapsacks.iter().zip(&problem.mp_capacities) {
+        assert_eq!(knapsack.ks_weight, knapsack.ks_items.iter().map(|item| item.item_weight).sum::<u32>());
+        assert_eq!(knapsack.ks_value, knapsack.ks_items.iter().map(|item| item.item_value).sum::<u32>());
+        assert_eq!(knapsack.ks_weight + knapsack.ks_capacity, capacity);
+        all_items.extend(knapsack.ks_items.iter().cloned());
+    }
+    assert!(vector_same_set_test(&all_items, &problem.mp_options));
+    assert_eq!(solution.ms_value, solution.ms_knapsacks.iter().map(|knapsack| knapsack.ks_value).sum::<u32>());
+}
+
+fn random_problem(max_items: usize, max_knapsacks: usize) -> MultipleProblem {
+    let mut rng = seeded_rng();
+    let knapsacks = rng.gen::<usize>() % (max_knapsacks + 1);
+    MultipleProblem {
+        mp_capacities: (0..knapsacks).map(|_| rng.gen::<u32>() % 60).collect(),
+        mp_options: light_items(max_items, 30),
+    }
+}
+
+#[test]
+fn multiple_handles_no_knapsacks() {
+    let item_options = vec![Item { item_weight: 1, item_value: 1 }];
+    let solution = multiple_knapsack(MultipleProblem {
+        mp_capacities: Vec::new(), mp_options: item_options.clone(),
+    }, MultipleStrategy::Exact);
+    assert_eq!(solution, MultipleSolution {
+        ms_knapsacks: Vec::new(), ms_unassigned: item_options, ms_value: 0,
+    });
+}
+
+#[test]
+fn multiple_exact_matches_exhaustive() {
+    let problem = random_problem(8, 3);
+    println!("Problem: {:?}", problem);
+    let solution = multiple_knapsack(problem.clone(), MultipleStrategy::Exact);
+    check_consistent(&problem, &solution);
+    assert_eq!(solution.ms_value, exhaustive_value(&problem));
+}
+
+#[test]
+fn multiple_single_matches_dynamic() {
+    let mut rng = seeded_rng();
+    let item_options = light_items(40, 100);
+    let capacity = rng.gen::<u32>() % 1000;
+    println!("Capacity: {}, Items: {:?}", capacity, item_options);
+
+    let solution = multiple_knapsack(MultipleProblem {
+        mp_capacities: vec![capacity], mp_options: item_options.clone(),
+    }, MultipleStrategy::Exact);
+    let expected = dynamic_knapsack(KnapsackProblem { kp_capacity: capacity, kp_options: item_options });
+    assert_eq!(solution.ms_value, expected.ks_value);
+}
+
+#[test]
+fn multiple_greedy_is_feasible() {
+    let problem = random_problem(10, 3);
+    println!("Problem: {:?}", problem);
+    let greedy = multiple_knapsack(problem.clone(), MultipleStrategy::Greedy);
+    check_consistent(&problem, &greedy);
+    assert!(greedy.ms_value <= multiple_knapsack(problem, MultipleStrategy::Exact).ms_value);
+}
+
+#[test]
+fn multiple_exact_sorts_past_worthless() {
+    let item_options = vec![
+        Item { item_weight: 14, item_value: 11 }, Item { item_weight: 16, item_value: 15 },
+        Item { item_weight: 19, item_value: 9 }, Item { item_weight: 13, item_value: 1 },
+        Item { item_weight: 8, item_value: 14 }, Item { item_weight: 0, item_value: 0 },
+        Item { item_weight: 11, item_value: 14 }, Item { item_weight: 0, item_value: 0 },
+        Item { item_weight: 19, item_value: 14 },
+    ];
+    let problem = MultipleProblem { mp_capacities: vec![20], mp_options: item_options };
+    let solution = multiple_knapsack(problem.clone(), MultipleStrategy::Exact);
+    check_consistent(&problem, &solution);
+    assert_eq!(solution.ms_value, exhaustive_value(&problem));
+}
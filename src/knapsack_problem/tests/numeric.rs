@@ -0,0 +1,61 @@
+use knapsack_problem::*;
+use test_utils::seeded_rng;
+use test_utils::rand::Rng;
+use super::light_items;
+
+#[test]
+fn wide_types_avoid_overflow() {
+    let item_options: Vec<Item<u64, u128>> = vec![
+        Item { item_weight: 1 << 40, item_value: u64::MAX as u128 },
+        Item { item_weight: 1 << 40, item_value: u64::MAX as u128 },
+        Item { item_weight: 1, item_value: 1 },
+    ];
+    let knapsack_problem = KnapsackProblem { kp_capacity: 1 << 41, kp_options: item_options.clone() };
+    assert_eq!(try_best_knapsack(knapsack_problem), Ok(KnapsackSolution {
+        ks_weight: 1 << 41,
+        ks_capacity: 0,
+        ks_value: 2 * u64::MAX as u128,
+        ks_items: item_options[..2].to_vec(),
+    }));
+}
+
+#[test]
+fn float_values_overflow_to_error() {
+    let knapsack_problem = KnapsackProblem {
+        kp_capacity: 2u8,
+        kp_options: vec![Item { item_weight: 1, item_value: f64::MAX },
+                         Item { item_weight: 1, item_value: f64::MAX }],
+    };
+    assert_eq!(try_best_knapsack(knapsack_problem), Err(KnapsackError::ValueOverflow(1)));
+}
+
+#[test]
+fn generic_solvers_agree() {
+    /*
+     * Scale the values of a random problem into floats, and the weights into a wider type, the
+     * chosen items shouldn't change.
+     */
+    let mut rng = seeded_rng();
+    let item_options = light_items(14, 60);
+    let capacity = rng.gen::<u32>() % 400;
+    println!("Capacity: {}, Items: {:?}", capacity, item_options);
+
+    let expected = best_knapsack(KnapsackProblem { kp_capacity: capacity, kp_options: item_options.clone() });
+    let scaled = KnapsackProblem {
+        kp_capacity: capacity as u64,
+        kp_options: item_options.iter()
+            .map(|item| Item { item_weight: item.item_weight as u64, item_value: item.item_value as f64 / 4.0 })
+            .collect(),
+    };
+    let expected_scaled = KnapsackSolution {
+        ks_weight: expected.ks_weight as u64,
+        ks_capacity: expected.ks_capacity as u64,
+        ks_value: expected.ks_value as f64 / 4.0,
+        ks_items: expected.ks_items.iter()
+            .map(|item| Item { item_weight: item.item_weight as u64, item_value: item.item_value as f64 / 4.0 })
+            .collect(),
+    };
+    assert_eq!(best_knapsack(scaled.clone()), expected_scaled);
+    assert_eq!(dynamic_knapsack(scaled.clone()), expected_scaled);
+    assert_eq!(hirschberg_knapsack(scaled), expected_scaled);
+}
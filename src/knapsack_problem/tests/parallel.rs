@@ -0,0 +1,137 @@
+use knapsack_problem::*;
+use test_utils::seeded_rng;
+use test_utils::rand::Rng;
+use super::light_items;
+
+const MAX_RECURSIVE_SIZE: usize = 16;
+
+#[test]
+fn parallel_handles_base_case() {
+    let knapsack_solution = parallel_knapsack(KnapsackProblem {
+        kp_capacity: 3, kp_options: Vec::new()
+    }, 4);
+    assert_eq!(knapsack_solution, KnapsackSolution {
+        ks_weight: 0, ks_value: 0, ks_capacity: 3, ks_items: Vec::new(),
+    });
+}
+
+#[test]
+fn parallel_matches_recursive() {
+    let mut rng = seeded_rng();
+    let item_options = light_items(MAX_RECURSIVE_SIZE, 100);
+    let capacity = rng.gen::<u32>() % 800;
+    println!("Capacity: {}, Items: {:?}", capacity, item_options);
+
+    let problem = KnapsackProblem { kp_capacity: capacity, kp_options: item_options };
+    assert_eq!(parallel_knapsack(problem.clone(), 3), best_knapsack(problem));
+}
+
+#[test]
+fn parallel_same_for_any_thread_count() {
+    /* Wide enough that the rows really are split between threads. */
+    let mut rng = seeded_rng();
+    let item_options = light_items(40, 2000);
+    let capacity = 10000 + rng.gen::<u32>() % 20000;
+    println!("Capacity: {}, Items: {:?}", capacity, item_options);
+
+    let problem  = KnapsackProblem { kp_capacity: capacity, kp_options: item_options };
+    let expected = dynamic_knapsack(problem.clone());
+    for &threads in &[0, 1, 2, 3, 8] {
+        assert_eq!(parallel_knapsack(problem.clone(), threads), expected);
+    }
+}
+
+/*
+ * A value that panics when added past 100, to check a panic in one thread stops them all.
+ * The first time a sum comes to 3 it also dawdles, to make one thread late to a barrier.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+struct Fragile(u32);
+
+static FRAGILE_DAWDLED: ::std::sync::atomic::AtomicBool = ::std::sync::atomic::AtomicBool::new(false);
+
+impl ::std::ops::Add for Fragile {
+    type Output = Fragile;
+    fn add(self, other: Fragile) -> Fragile {
+        if self.0 + other.0 > 100 {
+            panic!("Fragile value added past 100");
+        }
+        if self.0 + other.0 == 3 && !FRAGILE_DAWDLED.swap(true, ::std::sync::atomic::Ordering::Relaxed) {
+            ::std::thread::sleep(::std::time::Duration::from_millis(200));
+        }
+        Fragile(self.0 + other.0)
+    }
+}
+
+impl KnapsackValue for Fragile {
+    fn zero() -> Self { Fragile(0) }
+    fn checked_add(self, other: Self) -> Option<Self> { Some(self + other) }
+}
+
+#[test]
+#[should_panic(expected = "added past 100")]
+fn parallel_passes_on_panics() {
+    /* Only the widest columns can hold all three items, and they belong to the last thread. */
+    let problem = KnapsackProblem {
+        kp_capacity: 60000u32,
+        kp_options: vec![Item { item_weight: 20000, item_value: Fragile(40) }; 3],
+    };
+    parallel_knapsack(problem, 4);
+}
+
+#[test]
+fn parallel_passes_on_late_panics() {
+    /*
+     * Only the last thread's columns can hold the second item, so it's the last to finish that
+     * row, and then panics on the third item before the other threads have woken up.
+     * Getting that wrong leaves the threads waiting at the barrier forever, so we solve on a
+     * thread of our own and give up on it if it doesn't finish.
+     */
+    let problem = KnapsackProblem {
+        kp_capacity: 60000u32,
+        kp_options: vec![Item { item_weight: 1, item_value: Fragile(1) },
+                         Item { item_weight: 46000, item_value: Fragile(2) },
+                         Item { item_weight: 1, item_value: Fragile(98) }],
+    };
+    let (sender, receiver) = ::std::sync::mpsc::channel();
+    ::std::thread::spawn(move || {
+        let outcome = ::std::panic::catch_unwind(move || parallel_knapsack(problem, 4));
+        let message = outcome.err().and_then(|payload| payload.downcast_ref::<&str>().map(|text| text.to_string()));
+        sender.send(message).unwrap();
+    });
+    let message = receiver.recv_timeout(::std::time::Duration::from_secs(10))
+        .expect("parallel_knapsack() never finished");
+    assert_eq!(message, Some("Fragile value added past 100".to_string()));
+}
+
+#[test]
+#[should_panic(expected = "value overflowed")]
+fn parallel_panics_on_overflow() {
+    parallel_knapsack(KnapsackProblem {
+        kp_capacity: 2,
+        kp_options: vec![Item { item_weight: 1, item_value: u32::MAX },
+                         Item { item_weight: 1, item_value: 1 }],
+    }, 2);
+}
+
+#[test]
+#[ignore]
+fn parallel_timing() {
+    /*
+     * Not a real test, run it with `--ignored --nocapture` to see how long each solver takes on
+     * a problem with wide rows.
+     * With more than one core the parallel solver should be the faster one.
+     */
+    let mut rng = seeded_rng();
+    let item_options = (0..64)
+        .map(|_| Item { item_weight: rng.gen::<u32>() % (1 << 16), item_value: rng.gen::<u32>() % 1000 })
+        .collect();
+    let problem = KnapsackProblem { kp_capacity: 1 << 19, kp_options: item_options };
+    let start = ::std::time::Instant::now();
+    let expected = dynamic_knapsack(problem.clone());
+    let single = start.elapsed();
+    let start = ::std::time::Instant::now();
+    assert_eq!(parallel_knapsack(problem.clone(), 0), expected);
+    println!("{} items: dynamic_knapsack() took {:?}, parallel_knapsack() took {:?}",
+             problem.kp_options.len(), single, start.elapsed());
+}
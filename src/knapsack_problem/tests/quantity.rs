@@ -0,0 +1,136 @@
+use knapsack_problem::*;
+use test_utils::seeded_rng;
+use test_utils::rand::Rng;
+use super::light_items;
+
+fn random_copies(max_length: usize) -> Vec<QuantityItem> {
+    let mut rng = seeded_rng();
+    light_items(max_length, 30).into_iter()
+        .map(|item| QuantityItem { qi_item: item, qi_copies: rng.gen::<u32>() % 10 })
+        .collect()
+}
+
+fn check_consistent(solution: &QuantitySolution, capacity: u32) {
+    let weight: u32 = solution.qs_items.iter()
+        .map(|copies| copies.qi_item.item_weight * copies.qi_copies).sum();
+    let value: u32 = solution.qs_items.iter()
+        .map(|copies| copies.qi_item.item_value * copies.qi_copies).sum();
+    assert_eq!(solution.qs_weight, weight);
+    assert_eq!(solution.qs_value, value);
+    assert_eq!(solution.qs_weight + solution.qs_capacity, capacity);
+    assert!(solution.qs_items.iter().all(|copies| copies.qi_copies != 0));
+}
+
+#[test]
+fn bounded_handles_weightless() {
+    let solution = bounded_knapsack(QuantityProblem {
+        qp_capacity: 0,
+        qp_options: vec![
+            QuantityItem { qi_item: Item { item_weight: 0, item_value: 3 }, qi_copies: 7 },
+            QuantityItem { qi_item: Item { item_weight: 1, item_value: 3 }, qi_copies: 7 },
+        ],
+    });
+    assert_eq!(solution, QuantitySolution {
+        qs_weight: 0,
+        qs_capacity: 0,
+        qs_value: 21,
+        qs_items: vec![
+            QuantityItem { qi_item: Item { item_weight: 0, item_value: 3 }, qi_copies: 7 },
+        ],
+    });
+}
+
+#[test]
+fn bounded_matches_duplicated() {
+    /* Writing out each copy as a separate item should give the same best value. */
+    let mut rng = seeded_rng();
+    let options = random_copies(20);
+    let capacity = rng.gen::<u32>() % 500;
+    println!("Capacity: {}, Items: {:?}", capacity, options);
+
+    let duplicated: Vec<Item> = options.iter()
+        .flat_map(|copies| vec![copies.qi_item.clone(); copies.qi_copies as usize])
+        .collect();
+    let solution = bounded_knapsack(QuantityProblem { qp_capacity: capacity, qp_options: options.clone() });
+    check_consistent(&solution, capacity);
+    for copies in &solution.qs_items {
+        let available = options.iter().find(|option| option.qi_item == copies.qi_item).unwrap();
+        assert!(copies.qi_copies <= available.qi_copies);
+    }
+    assert_eq!(solution.qs_value,
+               dynamic_knapsack(KnapsackProblem { kp_capacity: capacity, kp_options: duplicated }).ks_value);
+}
+
+#[test]
+fn unbounded_matches_bounded() {
+    /* With as many copies as could possibly fit, the bounded problem is the unbounded one. */
+    let mut rng = seeded_rng();
+    let capacity = rng.gen::<u32>() % 500;
+    let item_options: Vec<Item> = light_items(20, 30).into_iter()
+        .filter(|item| item.item_weight != 0)
+        .collect();
+    println!("Capacity: {}, Items: {:?}", capacity, item_options);
+
+    let solution = unbounded_knapsack(KnapsackProblem { kp_capacity: capacity, kp_options: item_options.clone() });
+    check_consistent(&solution, capacity);
+    let bounded = bounded_knapsack(QuantityProblem {
+        qp_capacity: capacity,
+        qp_options: item_options.into_iter()
+            .map(|item| QuantityItem { qi_copies: capacity / item.item_weight, qi_item: item })
+            .collect(),
+    });
+    assert_eq!(solution.qs_value, bounded.qs_value);
+}
+
+#[test]
+#[should_panic(expected = "infinitely valuable")]
+fn unbounded_rejects_weightless() {
+    unbounded_knapsack(KnapsackProblem {
+        kp_capacity: 3, kp_options: vec![Item { item_weight: 0, item_value: 1 }],
+    });
+}
+
+#[test]
+fn unbounded_scales_by_common_factor() {
+    /* Multiplying every weight and the capacity by the same factor shouldn't change the items. */
+    let mut rng = seeded_rng();
+    let capacity = rng.gen::<u32>() % 500;
+    let item_options: Vec<Item> = light_items(20, 30).into_iter()
+        .filter(|item| item.item_weight != 0)
+        .collect();
+    println!("Capacity: {}, Items: {:?}", capacity, item_options);
+
+    let scale = 1 << 23;
+    let scaled = unbounded_knapsack(KnapsackProblem {
+        kp_capacity: capacity * scale,
+        kp_options: item_options.iter()
+            .map(|item| Item { item_weight: item.item_weight * scale, item_value: item.item_value })
+            .collect(),
+    });
+    let solution = unbounded_knapsack(KnapsackProblem { kp_capacity: capacity, kp_options: item_options });
+    check_consistent(&scaled, capacity * scale);
+    assert_eq!(scaled.qs_value, solution.qs_value);
+    assert_eq!(scaled.qs_weight, solution.qs_weight * scale);
+}
+
+#[test]
+fn quantity_reports_overflow() {
+    let heavy = Item { item_weight: 1 << 30, item_value: 1 << 31 };
+    let problem = KnapsackProblem { kp_capacity: u32::MAX, kp_options: vec![heavy.clone()] };
+    assert_eq!(try_unbounded_knapsack(problem), Err(KnapsackError::ValueOverflow(0)));
+    let problem = KnapsackProblem { kp_capacity: 1 << 30, kp_options: vec![heavy.clone()] };
+    assert_eq!(try_unbounded_knapsack(problem).unwrap().qs_value, 1 << 31);
+
+    /* One bundle of copies overflowing, and two bundles that only overflow together. */
+    let light = Item { item_weight: 1, item_value: 1 << 31 };
+    let copies = |qi_item: &Item, qi_copies| QuantityItem { qi_item: qi_item.clone(), qi_copies };
+    let problem = QuantityProblem { qp_capacity: 10, qp_options: vec![copies(&light, 3)] };
+    assert_eq!(try_bounded_knapsack(problem), Err(KnapsackError::ValueOverflow(0)));
+    let problem = QuantityProblem {
+        qp_capacity: 10,
+        qp_options: vec![copies(&Item { item_weight: 1, item_value: 5 }, 1), copies(&light, 2)],
+    };
+    assert_eq!(try_bounded_knapsack(problem), Err(KnapsackError::ValueOverflow(1)));
+    let problem = QuantityProblem { qp_capacity: 1, qp_options: vec![copies(&light, 2)] };
+    assert_eq!(try_bounded_knapsack(problem).unwrap().qs_value, 1 << 31);
+}
@@ -0,0 +1,98 @@
+use knapsack_problem::*;
+use test_utils::seeded_rng;
+use test_utils::rand::Rng;
+use super::light_items;
+
+/* Values of every subset that fits, best first, only usable for a handful of items. */
+fn exhaustive_values(problem: &KnapsackProblem) -> Vec<u32> {
+    let count = problem.kp_options.len();
+    let mut values: Vec<u32> = (0..(1u32 << count))
+        .filter_map(|subset| {
+            let chosen: Vec<&Item> = (0..count)
+                .filter(|index| subset & (1 << index) != 0)
+                .map(|index| &problem.kp_options[index])
+                .collect();
+            let weight: u32 = chosen.iter().map(|item| item.item_weight).sum();
+            if weight <= problem.kp_capacity {
+                Some(chosen.iter().map(|item| item.item_value).sum())
+            } else {
+                None
+            }
+        })
+        .collect();
+    values.sort_by(|left, right| right.cmp(left));
+    values
+}
+
+#[test]
+fn ranked_matches_exhaustive() {
+    /*
+     * Every item is distinct (by value) so that no subsets are merged, then every subset that
+     * fits should come out exactly once, in order.
+     */
+    let mut rng = seeded_rng();
+    let item_options: Vec<Item> = light_items(12, 20).into_iter()
+        .enumerate()
+        .map(|(index, item)| Item { item_value: (item.item_value % 100) * 16 + index as u32, ..item })
+        .collect();
+    let problem = KnapsackProblem { kp_capacity: rng.gen::<u32>() % 100, kp_options: item_options };
+    println!("Problem: {:?}", problem);
+
+    let solutions: Vec<KnapsackSolution> = ranked_knapsacks(problem.clone()).collect();
+    let values: Vec<u32> = solutions.iter().map(|solution| solution.ks_value).collect();
+    assert_eq!(values, exhaustive_values(&problem));
+    for solution in &solutions {
+        assert!(solution.option_indices(&problem.kp_options).is_some());
+        assert_eq!(solution.ks_weight + solution.ks_capacity, problem.kp_capacity);
+    }
+}
+
+#[test]
+fn ranked_merges_identical_items() {
+    let item = Item { item_weight: 1, item_value: 1 };
+    let problem = KnapsackProblem { kp_capacity: 2, kp_options: vec![item.clone(); 3] };
+    let solutions: Vec<KnapsackSolution> = ranked_knapsacks(problem).collect();
+    assert_eq!(solutions, vec![
+        KnapsackSolution { ks_weight: 2, ks_capacity: 0, ks_value: 2, ks_items: vec![item.clone(); 2] },
+        KnapsackSolution { ks_weight: 1, ks_capacity: 1, ks_value: 1, ks_items: vec![item] },
+        KnapsackSolution { ks_weight: 0, ks_capacity: 2, ks_value: 0, ks_items: Vec::new() },
+    ]);
+}
+
+#[test]
+fn optimal_includes_dynamic() {
+    let mut rng = seeded_rng();
+    let problem = KnapsackProblem {
+        kp_capacity: rng.gen::<u32>() % 200,
+        kp_options: light_items(40, 20).into_iter()
+            .map(|item| Item { item_value: item.item_value % 4, ..item })
+            .collect(),
+    };
+    println!("Problem: {:?}", problem);
+
+    let expected = dynamic_knapsack(problem.clone());
+    let optimal: Vec<KnapsackSolution> = optimal_knapsacks(problem).take(50).collect();
+    assert!(!optimal.is_empty());
+    assert!(optimal.iter().all(|solution| solution.ks_value == expected.ks_value));
+    if optimal.len() < 50 {
+        assert!(optimal.contains(&expected));
+    }
+    for (index, solution) in optimal.iter().enumerate() {
+        assert!(!optimal[..index].contains(solution));
+    }
+}
+
+#[test]
+fn ranked_skips_overflowing_values() {
+    /* Any two of the first three items overflow together, as do both copies of the last. */
+    let problem = KnapsackProblem {
+        kp_capacity: 3,
+        kp_options: vec![Item { item_weight: 1, item_value: u32::MAX },
+                         Item { item_weight: 1, item_value: 1 << 31 },
+                         Item { item_weight: 2, item_value: 1 << 31 },
+                         Item { item_weight: 1, item_value: 1 << 31 }],
+    };
+    let values: Vec<u32> = ranked_knapsacks(problem.clone()).map(|solution| solution.ks_value).collect();
+    assert_eq!(values, vec![u32::MAX, 1 << 31, 1 << 31, 0]);
+    assert_eq!(optimal_knapsacks(problem).count(), 1);
+}
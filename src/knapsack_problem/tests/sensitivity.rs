@@ -0,0 +1,115 @@
+use knapsack_problem::*;
+use test_utils::seeded_rng;
+use test_utils::rand::Rng;
+use super::light_items;
+
+/* Value of the items at `indices` of `options`, with the value of item `changed` set to `value`. */
+fn value_of(options: &[Item], indices: &[usize], changed: usize, value: u32) -> u32 {
+    indices.iter().map(|&index| if index == changed { value } else { options[index].item_value }).sum()
+}
+
+/* Whether the items at `indices` are still optimal with the value of item `changed` set to `value`. */
+fn still_optimal(problem: &KnapsackProblem, indices: &[usize], changed: usize, value: u32) -> bool {
+    let mut changed_problem = problem.clone();
+    changed_problem.kp_options[changed].item_value = value;
+    let optimum = dynamic_knapsack(changed_problem).ks_value;
+    value_of(&problem.kp_options, indices, changed, value) == optimum
+}
+
+#[test]
+fn sensitivity_limits_are_exact() {
+    let mut rng = seeded_rng();
+    let problem = KnapsackProblem { kp_capacity: rng.gen::<u32>() % 400, kp_options: light_items(12, 100) };
+    println!("Problem: {:?}", problem);
+
+    let solution = dynamic_knapsack(problem.clone());
+    let indices  = solution.option_indices(&problem.kp_options).unwrap();
+    let report   = sensitivity_analysis(&problem, &solution).unwrap();
+    assert_eq!(report.sr_items.len(), problem.kp_options.len());
+    for (index, sensitivity) in report.sr_items.iter().enumerate() {
+        let value = problem.kp_options[index].item_value;
+        assert_eq!(sensitivity.is_taken, indices.contains(&index));
+        match sensitivity.is_decrease {
+            Some(decrease) => {
+                assert!(still_optimal(&problem, &indices, index, value - decrease));
+                if decrease < value {
+                    assert!(!still_optimal(&problem, &indices, index, value - decrease - 1));
+                }
+            },
+            None => assert!(still_optimal(&problem, &indices, index, 0)),
+        }
+        match sensitivity.is_increase {
+            Some(increase) => {
+                assert!(still_optimal(&problem, &indices, index, value + increase));
+                assert!(!still_optimal(&problem, &indices, index, value + increase + 1));
+            },
+            None => assert!(still_optimal(&problem, &indices, index, value + 1000)),
+        }
+    }
+
+    /* Shrinking by any more would lose value. */
+    let shrunk = problem.kp_capacity - report.sr_capacity_shrink;
+    let best_at = |capacity| dynamic_knapsack(KnapsackProblem { kp_capacity: capacity, kp_options: problem.kp_options.clone() }).ks_value;
+    assert_eq!(best_at(shrunk), solution.ks_value);
+    assert!(shrunk <= solution.ks_weight);
+    if shrunk > 0 {
+        assert!(best_at(shrunk - 1) < solution.ks_value);
+    }
+}
+
+#[test]
+fn sensitivity_rejects_non_optimal() {
+    let problem = KnapsackProblem {
+        kp_capacity: 5,
+        kp_options: vec![Item { item_weight: 3, item_value: 4 }, Item { item_weight: 2, item_value: 3 }],
+    };
+    let partial = KnapsackSolution {
+        ks_weight: 3, ks_capacity: 2, ks_value: 4, ks_items: vec![problem.kp_options[0].clone()],
+    };
+    assert!(sensitivity_analysis(&problem, &partial).is_none());
+    let foreign = KnapsackSolution {
+        ks_weight: 5, ks_capacity: 0, ks_value: 7, ks_items: vec![Item { item_weight: 5, item_value: 7 }],
+    };
+    assert!(sensitivity_analysis(&problem, &foreign).is_none());
+    let miscounted = KnapsackSolution {
+        ks_weight: 4, ks_capacity: 1, ks_value: 7, ks_items: problem.kp_options.clone(),
+    };
+    assert!(sensitivity_analysis(&problem, &miscounted).is_none());
+    let overvalued = KnapsackSolution {
+        ks_weight: 3, ks_capacity: 2, ks_value: 7, ks_items: vec![problem.kp_options[0].clone()],
+    };
+    assert!(sensitivity_analysis(&problem, &overvalued).is_none());
+}
+
+#[test]
+fn sensitivity_capacity_slack() {
+    /* Nothing left to add, so the capacity can shrink down to the weight of the solution. */
+    let problem = KnapsackProblem {
+        kp_capacity: 100,
+        kp_options: vec![Item { item_weight: 30, item_value: 4 }, Item { item_weight: 20, item_value: 3 }],
+    };
+    let report = sensitivity_analysis(&problem, &dynamic_knapsack(problem.clone())).unwrap();
+    assert_eq!(report.sr_capacity_shrink, 50);
+    assert!(report.sr_items.iter().all(|item| item.is_taken && item.is_increase.is_none()));
+    assert_eq!(report.sr_items[1].is_decrease, Some(3));
+}
+
+#[test]
+fn sensitivity_capacity_of_heavier_optimum() {
+    /*
+     * Both items are optimal on their own, and the optimum stays the same until the lighter one
+     * doesn't fit, whichever of them the solution took.
+     */
+    let problem = KnapsackProblem {
+        kp_capacity: 4,
+        kp_options: vec![Item { item_weight: 3, item_value: 4 }, Item { item_weight: 4, item_value: 4 }],
+    };
+    let heavier = KnapsackSolution {
+        ks_weight: 4, ks_capacity: 0, ks_value: 4, ks_items: vec![problem.kp_options[1].clone()],
+    };
+    assert_eq!(sensitivity_analysis(&problem, &heavier).unwrap().sr_capacity_shrink, 1);
+    let lighter = KnapsackSolution {
+        ks_weight: 3, ks_capacity: 1, ks_value: 4, ks_items: vec![problem.kp_options[0].clone()],
+    };
+    assert_eq!(sensitivity_analysis(&problem, &lighter).unwrap().sr_capacity_shrink, 1);
+}
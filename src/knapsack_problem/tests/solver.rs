@@ -0,0 +1,138 @@
+use std::time::{Duration, Instant};
+use knapsack_problem::*;
+use test_utils::seeded_rng;
+use test_utils::rand::Rng;
+use super::light_items;
+
+const MAX_RECURSIVE_SIZE: usize = 16;
+
+fn solvers() -> Vec<Box<dyn KnapsackSolver>> {
+    vec![Box::new(RecursiveSolver), Box::new(DynamicSolver), Box::new(BranchBoundSolver), Box::new(AutoSolver)]
+}
+
+#[test]
+fn solvers_find_optimal_value() {
+    let mut rng = seeded_rng();
+    let problem = KnapsackProblem { kp_capacity: rng.gen::<u32>() % 800, kp_options: light_items(MAX_RECURSIVE_SIZE, 100) };
+    println!("Problem: {:?}", problem);
+
+    let best = best_knapsack(problem.clone());
+    assert_eq!(RecursiveSolver.solve(problem.clone()), best);
+    for solver in solvers() {
+        let limited = solver.solve_with_limits(problem.clone(), &SolverLimits::default());
+        assert!(limited.ls_optimal, "{} not optimal", solver.name());
+        assert_eq!(limited.ls_solution.ks_value, best.ks_value, "{} failed", solver.name());
+    }
+}
+
+#[test]
+fn solvers_respect_node_limit() {
+    let mut rng = seeded_rng();
+    let problem = KnapsackProblem { kp_capacity: rng.gen::<u32>() % 800, kp_options: light_items(MAX_RECURSIVE_SIZE, 100) };
+    println!("Problem: {:?}", problem);
+
+    let best = best_knapsack(problem.clone());
+    let limits = SolverLimits { sl_max_nodes: Some(5), ..SolverLimits::default() };
+    for solver in solvers() {
+        let limited = solver.solve_with_limits(problem.clone(), &limits);
+        let solution = &limited.ls_solution;
+        assert!(solution.option_indices(&problem.kp_options).is_some());
+        assert_eq!(solution.ks_weight + solution.ks_capacity, problem.kp_capacity);
+        assert!(solution.ks_value <= best.ks_value);
+        if limited.ls_optimal {
+            assert_eq!(solution.ks_value, best.ks_value);
+        }
+    }
+
+    let no_nodes = SolverLimits { sl_max_nodes: Some(0), ..SolverLimits::default() };
+    if !problem.kp_options.is_empty() {
+        assert!(!RecursiveSolver.solve_with_limits(problem.clone(), &no_nodes).ls_optimal);
+        assert!(!BranchBoundSolver.solve_with_limits(problem, &no_nodes).ls_optimal);
+    }
+}
+
+#[test]
+fn auto_solver_choices() {
+    let small = KnapsackProblem { kp_capacity: u32::MAX, kp_options: vec![Item { item_weight: 1, item_value: 1 }; 5] };
+    assert_eq!(AutoSolver.choose(&small).name(), "recursive");
+    let narrow = KnapsackProblem { kp_capacity: 1000, kp_options: vec![Item { item_weight: 10, item_value: 1 }; 50] };
+    assert_eq!(AutoSolver.choose(&narrow).name(), "dynamic");
+    let wide = KnapsackProblem { kp_capacity: u32::MAX, kp_options: vec![Item { item_weight: 1 << 28, item_value: 1 }; 50] };
+    assert_eq!(AutoSolver.choose(&wide).name(), "branch-and-bound");
+    assert_eq!(AutoSolver.solve(wide).ks_value, 15);
+}
+
+#[test]
+fn solvers_stop_at_deadline() {
+    /* Far too many nodes to search, so only the deadline stops these. */
+    let problem = KnapsackProblem {
+        kp_capacity: 1_000_000,
+        kp_options: light_items(60, 100000).into_iter().filter(|item| item.item_weight != 0).collect(),
+    };
+    let start  = Instant::now();
+    let limits = SolverLimits { sl_deadline: Some(start + Duration::from_millis(50)), ..SolverLimits::default() };
+    let limited = RecursiveSolver.solve_with_limits(problem.clone(), &limits);
+    assert!(start.elapsed() < Duration::from_secs(5));
+    if problem.kp_options.len() > 40 {
+        assert!(!limited.ls_optimal);
+    }
+    assert!(limited.ls_solution.option_indices(&problem.kp_options).is_some());
+}
+
+#[test]
+fn solvers_stop_when_cancelled() {
+    let problem = KnapsackProblem { kp_capacity: 100, kp_options: light_items(MAX_RECURSIVE_SIZE, 100) };
+    let token = CancellationToken::new();
+    token.clone().cancel();
+    assert!(token.is_cancelled());
+    let limits = SolverLimits { sl_cancel: Some(token), ..SolverLimits::default() };
+    for solver in solvers() {
+        let limited = solver.solve_with_limits(problem.clone(), &limits);
+        if solver.name() != "dynamic" && solver.name() != "auto" && !problem.kp_options.is_empty() {
+            assert!(!limited.ls_optimal, "{} didn't stop", solver.name());
+            assert_eq!(limited.ls_solution.ks_value, 0);
+        }
+    }
+}
+
+#[test]
+fn recursive_solver_keeps_best_found() {
+    /* The first item is the last one tried, so only the chain down to it gets searched. */
+    let mut item_options = vec![Item { item_weight: 5, item_value: 100 }];
+    item_options.extend(vec![Item { item_weight: 1, item_value: 1 }; 10]);
+    let problem = KnapsackProblem { kp_capacity: 5, kp_options: item_options };
+    let limits = SolverLimits { sl_max_nodes: Some(11), ..SolverLimits::default() };
+    let limited = RecursiveSolver.solve_with_limits(problem.clone(), &limits);
+    assert!(!limited.ls_optimal);
+    assert_eq!(limited.ls_solution.ks_items, vec![Item { item_weight: 5, item_value: 100 }]);
+
+    /* Allowing more nodes can only find better solutions. */
+    let mut rng = seeded_rng();
+    let problem = KnapsackProblem { kp_capacity: rng.gen::<u32>() % 200, kp_options: light_items(10, 50) };
+    println!("Problem: {:?}", problem);
+    let mut previous = 0;
+    for max_nodes in 0..300 {
+        let limits = SolverLimits { sl_max_nodes: Some(max_nodes), ..SolverLimits::default() };
+        let value = RecursiveSolver.solve_with_limits(problem.clone(), &limits).ls_solution.ks_value;
+        assert!(value >= previous, "{} nodes found {}, fewer found {}", max_nodes, value, previous);
+        previous = value;
+    }
+}
+
+#[test]
+fn solvers_sort_past_worthless() {
+    /* Wide enough that `AutoSolver` uses branch and bound. */
+    let scale = 1 << 26;
+    let mut item_options: Vec<Item> = [(14, 11), (16, 15), (19, 9), (13, 1), (8, 14), (0, 0), (11, 14), (0, 0), (19, 14)]
+        .iter()
+        .map(|&(weight, item_value)| Item { item_weight: weight * scale, item_value })
+        .collect();
+    item_options.extend(vec![Item { item_weight: 0, item_value: 0 }; 4]);
+    let problem = KnapsackProblem { kp_capacity: 20 * scale, kp_options: item_options };
+    assert_eq!(AutoSolver.choose(&problem).name(), "branch-and-bound");
+    for solver in [Box::new(BranchBoundSolver) as Box<dyn KnapsackSolver>, Box::new(AutoSolver)].iter() {
+        let limited = solver.solve_with_limits(problem.clone(), &SolverLimits::default());
+        assert!(limited.ls_optimal, "{} not optimal", solver.name());
+        assert_eq!(limited.ls_solution.ks_value, 28, "{} failed", solver.name());
+    }
+}
@@ -0,0 +1,115 @@
+use knapsack_problem::*;
+use test_utils::seeded_rng;
+use test_utils::rand::Rng;
+use super::light_items;
+
+const MAX_EXHAUSTIVE_SIZE: usize = 14;
+
+/* Total weight of every subset of `options`, indexed by the bits of the subset. */
+fn subset_weights(options: &[Item]) -> Vec<u32> {
+    (0..1usize << options.len())
+        .map(|subset| options.iter()
+             .enumerate()
+             .filter(|&(index, _)| subset & (1 << index) != 0)
+             .map(|(_, item)| item.item_weight)
+             .sum())
+        .collect()
+}
+
+#[test]
+fn subset_sum_matches_exhaustive() {
+    let mut rng = seeded_rng();
+    let options = light_items(MAX_EXHAUSTIVE_SIZE, 50);
+    let target  = rng.gen::<u32>() % 300;
+    println!("Target: {}, Options: {:?}", target, options);
+
+    let weights = subset_weights(&options);
+    let count   = weights.iter().filter(|&&weight| weight == target).count();
+    assert_eq!(count_subset_sums(&options, target), count as u128);
+    match subset_sum(options.clone(), target) {
+        Some(solution) => {
+            assert!(count > 0);
+            assert_eq!(solution.ks_weight, target);
+            assert_eq!(solution.ks_capacity, 0);
+            assert!(solution.option_indices(&options).is_some());
+        },
+        None => assert_eq!(count, 0),
+    }
+}
+
+#[test]
+fn subset_sum_empty_target() {
+    assert_eq!(subset_sum(Vec::new(), 0), Some(KnapsackSolution {
+        ks_weight: 0, ks_capacity: 0, ks_value: 0, ks_items: Vec::new(),
+    }));
+    assert!(subset_sum(Vec::new(), 1).is_none());
+}
+
+#[test]
+fn change_matches_exhaustive() {
+    let mut rng = seeded_rng();
+    let coins: Vec<u32> = (0..1 + rng.gen::<usize>() % 4).map(|_| 1 + rng.gen::<u32>() % 12).collect();
+    let amount = rng.gen::<u32>() % 40;
+    println!("Amount: {}, Coins: {:?}", amount, coins);
+
+    /* Every way to use up to `amount` of each coin, which is plenty. */
+    let mut ways   = 0u128;
+    let mut fewest = None;
+    let mut counts = vec![0u32; coins.len()];
+    loop {
+        let total: u32 = counts.iter().zip(&coins).map(|(count, coin)| count * coin).sum();
+        if total == amount {
+            ways += 1;
+            let used: u32 = counts.iter().sum();
+            fewest = Some(fewest.map_or(used, |best: u32| best.min(used)));
+        }
+        match counts.iter().position(|&count| count < amount) {
+            Some(position) => {
+                counts[position] += 1;
+                for count in &mut counts[..position] {
+                    *count = 0;
+                }
+            },
+            None => break,
+        }
+    }
+
+    /* Duplicate coins make the same amounts in more ways. */
+    let mut distinct = coins.clone();
+    distinct.sort();
+    distinct.dedup();
+    if distinct.len() == coins.len() {
+        assert_eq!(count_change(&coins, amount), ways);
+    }
+    let change = make_change(&coins, amount);
+    assert_eq!(change.as_ref().map(|counts| counts.iter().sum::<u32>()), fewest);
+    if let Some(counts) = change {
+        assert_eq!(counts.iter().zip(&coins).map(|(count, coin)| count * coin).sum::<u32>(), amount);
+    }
+}
+
+#[test]
+#[should_panic(expected = "coin of zero")]
+fn count_change_rejects_zero_coin() {
+    count_change(&[0, 1], 3);
+}
+
+#[test]
+fn large_targets_stay_small() {
+    let options = vec![Item { item_weight: 3, item_value: 0 }, Item { item_weight: 5, item_value: 0 }];
+    assert_eq!(count_subset_sums(&options, u32::MAX), 0);
+    assert_eq!(count_subset_sums(&options, 8), 1);
+
+    /* Only multiples of the coins' common factor get a column. */
+    let scale = 1 << 28;
+    assert_eq!(count_change(&[scale, 2 * scale, 5 * scale], 5 * scale), 4);
+    assert_eq!(count_change(&[2 * scale, 4 * scale], 15 * scale), 0);
+    assert_eq!(make_change(&[scale, 3 * scale, 4 * scale], 6 * scale), Some(vec![0, 2, 0]));
+    assert_eq!(make_change(&[2 * scale], u32::MAX), None);
+
+    /* Coins bigger than the amount don't count towards the common factor. */
+    assert_eq!(make_change(&[2, 3], 2), Some(vec![1, 0]));
+    assert_eq!(count_change(&[2, 3], 2), 1);
+    assert_eq!(make_change(&[7], 0), Some(vec![0]));
+    assert_eq!(count_change(&[7], 0), 1);
+}
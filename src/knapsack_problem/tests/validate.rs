@@ -0,0 +1,42 @@
+use knapsack_problem::*;
+use test_utils::seeded_rng;
+use test_utils::rand::Rng;
+use super::light_items;
+
+#[test]
+fn validate_accepts_solvers() {
+    let mut rng = seeded_rng();
+    let problem = KnapsackProblem { kp_capacity: rng.gen::<u32>() % 800, kp_options: light_items(30, 100) };
+    println!("Problem: {:?}", problem);
+
+    assert!(validate(&problem, &dynamic_knapsack(problem.clone())).is_empty());
+    assert!(validate(&problem, &greedy_knapsack(problem.clone()).as_solution).is_empty());
+    assert!(validate(&problem, &branch_and_bound_knapsack(problem.clone()).0).is_empty());
+}
+
+#[test]
+fn validate_reports_totals() {
+    let problem = KnapsackProblem {
+        kp_capacity: 10,
+        kp_options: vec![Item { item_weight: 6, item_value: 1 }, Item { item_weight: 6, item_value: 2 }],
+    };
+    let solution = KnapsackSolution {
+        ks_weight: 6, ks_capacity: 5, ks_value: 2, ks_items: vec![problem.kp_options[0].clone()],
+    };
+    assert_eq!(validate(&problem, &solution), vec![SolutionViolation::WrongCapacity(4), SolutionViolation::WrongValue(1)]);
+
+    let solution = KnapsackSolution {
+        ks_weight: 12, ks_capacity: 0, ks_value: 3, ks_items: problem.kp_options.clone(),
+    };
+    assert_eq!(validate(&problem, &solution), vec![SolutionViolation::Overweight]);
+}
+
+#[test]
+fn validate_reports_overflow() {
+    let options = vec![Item { item_weight: 1u8, item_value: 200u8 }, Item { item_weight: 1, item_value: 100 }];
+    let problem = KnapsackProblem { kp_capacity: 2, kp_options: options.clone() };
+    let solution = KnapsackSolution { ks_weight: 2, ks_capacity: 0, ks_value: 255, ks_items: options };
+    let violations = validate(&problem, &solution);
+    assert_eq!(violations, vec![SolutionViolation::ValueOverflow]);
+    assert_eq!(violations[0].to_string(), "the total value of the items overflows");
+}
@@ -0,0 +1,118 @@
+/*
+ * Checking that a `KnapsackSolution` from somewhere else really is a filling of a given
+ * `KnapsackProblem`, and that its totals add up.
+ *
+ * Rather than stopping at the first problem we report everything that's wrong, so that whoever
+ * produced the solution can fix it all at once.
+ */
+
+use std::fmt;
+use super::{KnapsackProblem, KnapsackSolution, KnapsackValue, KnapsackWeight, matched_indices};
+
+/// The `SolutionViolation` type -- one way in which a `KnapsackSolution` isn't a valid filling of
+/// a `KnapsackProblem`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolutionViolation<W = u32, V = u32> {
+    /// The item at this index of `ks_items` isn't in `kp_options`, or there are more copies of
+    /// it in the solution than in the options.
+    UnknownItem(usize),
+    /// The items weigh more than `kp_capacity` altogether.
+    Overweight,
+    /// `ks_weight` isn't the total weight of the items, which is held.
+    WrongWeight(W),
+    /// `ks_capacity` isn't `kp_capacity - ks_weight`, which is held.
+    WrongCapacity(W),
+    /// The total value of the items doesn't fit in the value type.
+    ValueOverflow,
+    /// `ks_value` isn't the total value of the items, which is held.
+    WrongValue(V),
+}
+
+impl<W: fmt::Debug, V: fmt::Debug> fmt::Display for SolutionViolation<W, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SolutionViolation::UnknownItem(index) =>
+                write!(f, "item {} of the solution is not one of the unused options", index),
+            SolutionViolation::Overweight =>
+                write!(f, "the items weigh more than the capacity"),
+            SolutionViolation::WrongWeight(ref weight) =>
+                write!(f, "the weight should be {:?}, the total weight of the items", weight),
+            SolutionViolation::WrongCapacity(ref capacity) =>
+                write!(f, "the remaining capacity should be {:?}", capacity),
+            SolutionViolation::ValueOverflow =>
+                write!(f, "the total value of the items overflows"),
+            SolutionViolation::WrongValue(ref value) =>
+                write!(f, "the value should be {:?}, the total value of the items", value),
+        }
+    }
+}
+
+/// Returns every way in which `solution` isn't a valid filling of the knapsack in `problem`.
+///
+/// An empty list means the solution is valid: every item is one of the options (each option
+/// used at most once), they fit in the knapsack, and `ks_weight`, `ks_capacity` and `ks_value`
+/// match the items.
+/// This doesn't check that the solution is optimal.
+///
+/// ```
+/// use rust_algorithms::knapsack_problem::{validate, KnapsackProblem, KnapsackSolution, SolutionViolation, Item};
+///
+/// let problem = KnapsackProblem {
+///     kp_capacity: 10,
+///     kp_options: vec![Item { item_weight: 3, item_value: 4 },
+///                      Item { item_weight: 4, item_value: 5 }],
+/// };
+/// let solution = KnapsackSolution {
+///     ks_weight:   7,
+///     ks_capacity: 3,
+///     ks_value:    10,
+///     ks_items:    vec![Item { item_weight: 3, item_value: 4 },
+///                       Item { item_weight: 3, item_value: 4 },
+///                       Item { item_weight: 1, item_value: 1 }],
+/// };
+/// assert_eq!(validate(&problem, &solution), vec![SolutionViolation::UnknownItem(1),
+///                                               SolutionViolation::UnknownItem(2),
+///                                               SolutionViolation::WrongValue(9)]);
+/// ```
+pub fn validate<W, V>(problem: &KnapsackProblem<W, V>, solution: &KnapsackSolution<W, V>)
+    -> Vec<SolutionViolation<W, V>>
+    where W: KnapsackWeight, V: KnapsackValue {
+    let mut violations: Vec<SolutionViolation<W, V>> = matched_indices(&solution.ks_items, &problem.kp_options)
+        .into_iter()
+        .enumerate()
+        .filter(|&(_, matched)| matched.is_none())
+        .map(|(index, _)| SolutionViolation::UnknownItem(index))
+        .collect();
+
+    /*
+     * Adding up the weights can only overflow once they're over the capacity, so we stop adding
+     * there, and can't say what the weight should be.
+     */
+    let mut weight = Some(W::zero());
+    for item in &solution.ks_items {
+        weight = weight.and_then(|weight| {
+            if item.item_weight > problem.kp_capacity - weight { None } else { Some(weight + item.item_weight) }
+        });
+    }
+    match weight {
+        None => violations.push(SolutionViolation::Overweight),
+        Some(weight) => {
+            if weight != solution.ks_weight {
+                violations.push(SolutionViolation::WrongWeight(weight));
+            }
+            if solution.ks_weight <= problem.kp_capacity &&
+                    solution.ks_capacity != problem.kp_capacity - solution.ks_weight {
+                violations.push(SolutionViolation::WrongCapacity(problem.kp_capacity - solution.ks_weight));
+            }
+        },
+    }
+
+    let value = solution.ks_items.iter()
+        .try_fold(V::zero(), |acc, item| acc.checked_add(item.item_value));
+    match value {
+        None => violations.push(SolutionViolation::ValueOverflow),
+        Some(value) if value != solution.ks_value => violations.push(SolutionViolation::WrongValue(value)),
+        Some(_) => (),
+    }
+    violations
+}
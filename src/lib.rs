@@ -4,6 +4,11 @@
  * vimcmd: !cargo test
  */
 
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(all(test, feature = "serde"))]
+extern crate serde_json;
+
 pub mod knapsack_problem;
 pub mod string_word_swap;
 pub mod disjoint_set;
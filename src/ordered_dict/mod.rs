@@ -4,6 +4,8 @@ use std::iter::FromIterator;
 use self::order_iter::*;
 
 mod order_iter;
+#[cfg(feature = "serde")]
+mod serialize;
 
 #[cfg(test)]
 mod tests;
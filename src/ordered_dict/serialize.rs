@@ -0,0 +1,53 @@
+// Serialize an OrderedDict<> as a map, with the entries in insertion order.
+// Deserializing inserts the entries in the order they're read, so a round-trip keeps the order
+// (as long as the format keeps the order of map entries, which JSON does).
+
+use std::fmt;
+use std::marker::PhantomData;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::de::{MapAccess, Visitor};
+use serde::ser::SerializeMap;
+use super::OrderedDict;
+
+impl<K, V> Serialize for OrderedDict<K, V>
+where K: ::std::cmp::Eq + ::std::hash::Hash + Serialize,
+      V: Serialize {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.len()))?;
+        for (k, v) in self {
+            map.serialize_entry(k, v)?;
+        }
+        map.end()
+    }
+}
+
+struct OrderedDictVisitor<K, V> {
+    marker: PhantomData<(K, V)>,
+}
+
+impl<'de, K, V> Visitor<'de> for OrderedDictVisitor<K, V>
+where K: ::std::cmp::Eq + ::std::hash::Hash + Deserialize<'de>,
+      V: Deserialize<'de> {
+    type Value = OrderedDict<K, V>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+        let mut ret = OrderedDict::new();
+        ret.reserve(access.size_hint().unwrap_or(0));
+        while let Some((k, v)) = access.next_entry()? {
+            ret.insert(k, v);
+        }
+        Ok(ret)
+    }
+}
+
+impl<'de, K, V> Deserialize<'de> for OrderedDict<K, V>
+where K: ::std::cmp::Eq + ::std::hash::Hash + Deserialize<'de>,
+      V: Deserialize<'de> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(OrderedDictVisitor { marker: PhantomData })
+    }
+}
@@ -242,3 +242,17 @@ fn iterate_order_link_map() {
  *          --  After all method calls, the keys in self.underlying_hash and
  *              self.order_link_map are the same.
  */
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_keeps_order() {
+    let (mut mydict, _) = create_default();
+    mydict.insert(String::from("Another"), 3);
+    mydict.remove(&String::from("Test string"));
+
+    let json = ::serde_json::to_string(&mydict).expect("Could not serialize OrderedDict");
+    assert_eq!(json, r#"{"Hello world":10,"Other test":6,"Another":3}"#);
+    let read: OrderedDict<String, usize> = ::serde_json::from_str(&json)
+        .expect("Could not deserialize OrderedDict");
+    assert_eq!(read.iter().collect::<Vec<_>>(), mydict.iter().collect::<Vec<_>>());
+}